@@ -0,0 +1,165 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Attribute, Data, DeriveInput, Fields, LitStr};
+
+/// Field names containing one of these are redacted.
+pub const PATTERNS: &[&str] = &["password", "secret", "token", "key", "credential"];
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let mut patterns: Vec<String> = PATTERNS.iter().map(|p| p.to_string()).collect();
+    for attr in auto_redact_attrs(&input.attrs) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("pattern") {
+                let pattern: LitStr = meta.value()?.parse()?;
+                patterns.push(pattern.value().to_lowercase());
+                Ok(())
+            } else {
+                Err(meta.error("unsupported attribute, expected `pattern`"))
+            }
+        })?;
+    }
+
+    let ident = &input.ident;
+    let arms = match &input.data {
+        Data::Struct(data) => vec![arm(quote!(Self), ident, &data.fields, None, &patterns)?],
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| {
+                let name = &variant.ident;
+                let default = redacted(&variant.attrs)?;
+                arm(
+                    quote!(Self::#name),
+                    name,
+                    &variant.fields,
+                    default,
+                    &patterns,
+                )
+            })
+            .collect::<syn::Result<_>>()?,
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(ident, "unions are not supported"));
+        }
+    };
+
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(::core::fmt::Debug));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::core::fmt::Debug for #ident #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    })
+}
+
+/// A match arm, formatting the fields of a struct or variant.
+///
+/// Fields without an attribute use the default of the variant, if any, or else the patterns.
+fn arm(
+    path: TokenStream,
+    name: &syn::Ident,
+    fields: &Fields,
+    default: Option<bool>,
+    patterns: &[String],
+) -> syn::Result<TokenStream> {
+    let name = name.to_string();
+    let mut bindings = Vec::new();
+    let mut entries = Vec::new();
+
+    for (index, field) in fields.iter().enumerate() {
+        let binding = format_ident!("__field{index}");
+        let redacted = match redacted(&field.attrs)?.or(default) {
+            Some(redacted) => redacted,
+            None => field
+                .ident
+                .as_ref()
+                .is_some_and(|ident| matches(&ident.to_string(), patterns)),
+        };
+
+        let value = match redacted {
+            true => quote!(&::hide::__private::Placeholder),
+            false => quote!(#binding),
+        };
+        entries.push(match &field.ident {
+            Some(ident) => {
+                let field_name = ident.to_string();
+                bindings.push(quote!(#ident: #binding));
+                quote!(.field(#field_name, #value))
+            }
+            None => {
+                bindings.push(quote!(#binding));
+                quote!(.field(#value))
+            }
+        });
+    }
+
+    Ok(match fields {
+        Fields::Named(_) => quote! {
+            #path { #(#bindings),* } => f.debug_struct(#name) #(#entries)* .finish(),
+        },
+        Fields::Unnamed(_) => quote! {
+            #path ( #(#bindings),* ) => f.debug_tuple(#name) #(#entries)* .finish(),
+        },
+        Fields::Unit => quote! {
+            #path => f.write_str(#name),
+        },
+    })
+}
+
+/// Check for `#[auto_redact(hide)]` or `#[auto_redact(show)]` on a field or variant.
+fn redacted(attrs: &[Attribute]) -> syn::Result<Option<bool>> {
+    let mut result = None;
+    for attr in auto_redact_attrs(attrs) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("hide") {
+                result = Some(true);
+                Ok(())
+            } else if meta.path.is_ident("show") {
+                result = Some(false);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported attribute, expected `hide` or `show`"))
+            }
+        })?;
+    }
+    Ok(result)
+}
+
+fn auto_redact_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("auto_redact"))
+}
+
+/// Check if the field name contains one of the patterns, ignoring the case.
+pub fn matches(name: &str, patterns: &[String]) -> bool {
+    let name = name.trim_start_matches("r#").to_lowercase();
+    patterns
+        .iter()
+        .any(|pattern| name.contains(pattern.as_str()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        let patterns: Vec<String> = PATTERNS.iter().map(|p| p.to_string()).collect();
+
+        assert!(matches("password", &patterns));
+        assert!(matches("db_Password", &patterns));
+        assert!(matches("api_key", &patterns));
+        assert!(matches("refresh_token", &patterns));
+        assert!(!matches("username", &patterns));
+        assert!(!matches("pin", &patterns));
+        assert!(matches("pin", &["pin".to_string()]));
+    }
+}
@@ -0,0 +1,131 @@
+use crate::auto_redact::matches;
+use syn::{
+    meta::ParseNestedMeta, Data, DeriveInput, Fields, GenericArgument, LitStr, PathArguments, Type,
+};
+
+/// Wrapper types, which hide their value.
+const HIDDEN: &[&str] = &[
+    "ApiKey",
+    "BearerToken",
+    "GuardedHide",
+    "Hide",
+    "HideIn",
+    "HideLock",
+    "HideOnce",
+    "HideStrict",
+    "HideString",
+    "Labeled",
+    "LazyHide",
+    "Password",
+    "Sealed",
+    "SharedHide",
+    "SymmetricKey",
+    "VersionedHide",
+];
+
+/// Parse the arguments of `#[enforce(pattern = "…")]`.
+pub fn parse_attr(patterns: &mut Vec<String>, meta: ParseNestedMeta) -> syn::Result<()> {
+    if meta.path.is_ident("pattern") {
+        let pattern: LitStr = meta.value()?.parse()?;
+        patterns.push(pattern.value().to_lowercase());
+        Ok(())
+    } else {
+        Err(meta.error("unsupported attribute, expected `pattern`"))
+    }
+}
+
+pub fn expand(input: &mut DeriveInput, patterns: &[String]) -> syn::Result<()> {
+    let mut errors = Vec::new();
+
+    match &mut input.data {
+        Data::Struct(data) => check(&mut data.fields, patterns, &mut errors),
+        Data::Enum(data) => {
+            for variant in &mut data.variants {
+                check(&mut variant.fields, patterns, &mut errors);
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "unions are not supported",
+            ));
+        }
+    }
+
+    match errors.into_iter().reduce(|mut result, err| {
+        result.combine(err);
+        result
+    }) {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+/// Check the named fields, removing the `#[allow_plain]` attributes.
+fn check(fields: &mut Fields, patterns: &[String], errors: &mut Vec<syn::Error>) {
+    for field in fields.iter_mut() {
+        let len = field.attrs.len();
+        field
+            .attrs
+            .retain(|attr| !attr.path().is_ident("allow_plain"));
+        if field.attrs.len() != len {
+            continue;
+        }
+
+        let Some(ident) = &field.ident else {
+            continue;
+        };
+        if matches(&ident.to_string(), patterns) && !is_hidden(&field.ty) {
+            errors.push(syn::Error::new_spanned(
+                &field.ty,
+                format!(
+                    "field `{ident}` looks like a secret, wrap it in `Hide` or mark it with \
+                     `#[allow_plain]`"
+                ),
+            ));
+        }
+    }
+}
+
+/// Check if the type is one of the wrapper types, or an `Option` of one.
+pub fn is_hidden(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+    let Some(segment) = path.path.segments.last() else {
+        return false;
+    };
+
+    let name = segment.ident.to_string();
+    if HIDDEN.contains(&name.as_str()) {
+        return true;
+    }
+
+    match (name.as_str(), &segment.arguments) {
+        ("Option", PathArguments::AngleBracketed(args)) => args
+            .args
+            .iter()
+            .any(|arg| matches!(arg, GenericArgument::Type(ty) if is_hidden(ty))),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_is_hidden() {
+        assert!(is_hidden(&parse_quote!(Hide<String>)));
+        assert!(is_hidden(&parse_quote!(hide::HideStrict<String>)));
+        assert!(is_hidden(&parse_quote!(Option<::hide::Hide<String>>)));
+        assert!(is_hidden(&parse_quote!(hide::sealed::Sealed<String>)));
+        assert!(is_hidden(&parse_quote!(Option<hide::Password>)));
+        assert!(is_hidden(&parse_quote!(SymmetricKey)));
+        assert!(!is_hidden(&parse_quote!(String)));
+        assert!(!is_hidden(&parse_quote!(Option<String>)));
+        assert!(!is_hidden(&parse_quote!(Vec<Hide<String>>)));
+        assert!(!is_hidden(&parse_quote!(&'static str)));
+    }
+}
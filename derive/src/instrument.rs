@@ -0,0 +1,133 @@
+use crate::auto_redact::matches;
+use crate::enforce::is_hidden;
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{FnArg, Ident, ItemFn, LitStr, Pat, Token, Type};
+
+pub fn expand(attr: TokenStream, item: &ItemFn, patterns: &[String]) -> syn::Result<TokenStream> {
+    let mut patterns = patterns.to_vec();
+    let mut args = Vec::new();
+    let mut skip = Vec::<Ident>::new();
+    let mut skip_all = false;
+
+    for arg in split_args(attr) {
+        match arg.as_slice() {
+            [TokenTree::Ident(ident), TokenTree::Punct(eq), value]
+                if ident == "pattern" && eq.as_char() == '=' =>
+            {
+                let pattern: LitStr = syn::parse2(value.clone().into())?;
+                patterns.push(pattern.value().to_lowercase());
+            }
+            [TokenTree::Ident(ident), TokenTree::Group(group)]
+                if ident == "skip" && group.delimiter() == Delimiter::Parenthesis =>
+            {
+                let parser = Punctuated::<Ident, Token![,]>::parse_terminated;
+                skip.extend(syn::parse::Parser::parse2(parser, group.stream())?);
+            }
+            _ => {
+                skip_all |=
+                    matches!(arg.as_slice(), [TokenTree::Ident(ident)] if ident == "skip_all");
+                args.push(arg.into_iter().collect::<TokenStream>());
+            }
+        }
+    }
+
+    for input in &item.sig.inputs {
+        let FnArg::Typed(input) = input else {
+            continue;
+        };
+        let Pat::Ident(pat) = &*input.pat else {
+            continue;
+        };
+        let ident = &pat.ident;
+        if matches(&ident.to_string(), &patterns)
+            && !is_hidden(dereference(&input.ty))
+            && !skip.contains(ident)
+        {
+            skip.push(ident.clone());
+        }
+    }
+
+    if !skip.is_empty() && !skip_all {
+        args.push(quote!(skip(#(#skip),*)));
+    }
+
+    Ok(quote! {
+        #[::tracing::instrument(#(#args),*)]
+        #item
+    })
+}
+
+/// Split the arguments at top-level commas.
+fn split_args(attr: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut result = vec![];
+    let mut current = vec![];
+
+    for token in attr {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => {
+                result.push(std::mem::take(&mut current));
+            }
+            _ => current.push(token),
+        }
+    }
+    if !current.is_empty() {
+        result.push(current);
+    }
+
+    result
+}
+
+/// Remove references, as a `&Hide<T>` is recorded hidden as well.
+fn dereference(mut ty: &Type) -> &Type {
+    while let Type::Reference(reference) = ty {
+        ty = &reference.elem;
+    }
+    ty
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::auto_redact::PATTERNS;
+    use syn::parse_quote;
+
+    fn expand(attr: TokenStream, item: ItemFn) -> String {
+        let patterns: Vec<String> = PATTERNS.iter().map(|p| p.to_string()).collect();
+        super::expand(attr, &item, &patterns).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_skip() {
+        let item: ItemFn = parse_quote! {
+            fn login(conn: u32, user: &str, password: &str, token: &Hide<String>, pin: u16) {}
+        };
+
+        assert_eq!(
+            expand(quote!(level = "debug", skip(conn)), item.clone()),
+            quote!(#[::tracing::instrument(level = "debug", skip(conn, password))] #item)
+                .to_string()
+        );
+        assert_eq!(
+            expand(quote!(pattern = "pin"), item.clone()),
+            quote!(#[::tracing::instrument(skip(password, pin))] #item).to_string()
+        );
+        assert_eq!(
+            expand(quote!(skip_all, err), item.clone()),
+            quote!(#[::tracing::instrument(skip_all, err)] #item).to_string()
+        );
+    }
+
+    #[test]
+    fn test_no_skip() {
+        let item: ItemFn = parse_quote! {
+            fn connect(&self, url: &str, password: Option<Hide<String>>) {}
+        };
+
+        assert_eq!(
+            expand(quote!(fields(a.b = 1, c), ret), item.clone()),
+            quote!(#[::tracing::instrument(fields(a.b = 1, c), ret)] #item).to_string()
+        );
+    }
+}
@@ -0,0 +1,72 @@
+//! Derive macros for the [`hide`](https://docs.rs/hide) crate
+//!
+//! Don't use this crate directly, but enable the `derive` feature of `hide`.
+
+mod auto_redact;
+mod enforce;
+mod instrument;
+mod redact;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, ItemFn};
+
+/// Redact fields marked with `#[hide]` in `#[error("…")]` messages.
+#[proc_macro_attribute]
+pub fn redact(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(item as DeriveInput);
+
+    match redact::expand(&mut input) {
+        Ok(()) => quote::quote!(#input).into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Fail to compile if fields whose names look like they contain secrets aren't hidden.
+#[proc_macro_attribute]
+pub fn enforce(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut patterns: Vec<String> = auto_redact::PATTERNS
+        .iter()
+        .map(|p| p.to_string())
+        .collect();
+    let parser = syn::meta::parser(|meta| enforce::parse_attr(&mut patterns, meta));
+    parse_macro_input!(attr with parser);
+    let mut input = parse_macro_input!(item as DeriveInput);
+
+    match enforce::expand(&mut input, &patterns) {
+        Ok(()) => quote::quote!(#input).into(),
+        Err(err) => {
+            let err = err.to_compile_error();
+            quote::quote!(#input #err).into()
+        }
+    }
+}
+
+/// Instrument a function using `tracing`, skipping arguments whose names look like they contain
+/// secrets.
+#[proc_macro_attribute]
+pub fn instrument_redacted(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let patterns: Vec<String> = auto_redact::PATTERNS
+        .iter()
+        .map(|p| p.to_string())
+        .collect();
+    let input = parse_macro_input!(item as ItemFn);
+
+    match instrument::expand(attr.into(), &input, &patterns) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => {
+            let err = err.to_compile_error();
+            quote::quote!(#input #err).into()
+        }
+    }
+}
+
+/// Derive `Debug`, redacting fields whose names look like they contain secrets.
+#[proc_macro_derive(AutoRedact, attributes(auto_redact))]
+pub fn auto_redact(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+
+    match auto_redact::expand(&input) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,175 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use std::collections::HashSet;
+use syn::{Attribute, Data, DeriveInput, Fields, LitStr, Meta};
+
+const PLACEHOLDER: &str = "***";
+
+pub fn expand(input: &mut DeriveInput) -> syn::Result<()> {
+    match &mut input.data {
+        Data::Struct(data) => {
+            let hidden = take_hidden(&mut data.fields);
+            rewrite_attrs(&mut input.attrs, &hidden)
+        }
+        Data::Enum(data) => {
+            for variant in &mut data.variants {
+                let hidden = take_hidden(&mut variant.fields);
+                rewrite_attrs(&mut variant.attrs, &hidden)?;
+            }
+            Ok(())
+        }
+        Data::Union(_) => Err(syn::Error::new_spanned(
+            &input.ident,
+            "unions are not supported",
+        )),
+    }
+}
+
+/// Collect the members of all fields marked with `#[hide]`, removing the attribute.
+fn take_hidden(fields: &mut Fields) -> HashSet<String> {
+    let mut result = HashSet::new();
+
+    for (index, field) in fields.iter_mut().enumerate() {
+        let len = field.attrs.len();
+        field.attrs.retain(|attr| !attr.path().is_ident("hide"));
+        if field.attrs.len() != len {
+            result.insert(match &field.ident {
+                Some(ident) => ident.to_string(),
+                None => index.to_string(),
+            });
+        }
+    }
+
+    result
+}
+
+fn rewrite_attrs(attrs: &mut [Attribute], hidden: &HashSet<String>) -> syn::Result<()> {
+    if hidden.is_empty() {
+        return Ok(());
+    }
+
+    for attr in attrs {
+        if !attr.path().is_ident("error") {
+            continue;
+        }
+        if let Meta::List(list) = &mut attr.meta {
+            list.tokens = rewrite_error(list.tokens.clone(), hidden)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Rewrite the content of an `#[error("…", args…)]` attribute.
+fn rewrite_error(tokens: TokenStream, hidden: &HashSet<String>) -> syn::Result<TokenStream> {
+    let mut tokens = tokens.into_iter();
+
+    let Some(first) = tokens.next() else {
+        return Ok(TokenStream::new());
+    };
+
+    // `#[error(transparent)]`, `#[error(fmt = …)]`
+    let TokenTree::Literal(_) = &first else {
+        return Ok(std::iter::once(first).chain(tokens).collect());
+    };
+
+    let fmt: LitStr = syn::parse2(first.into_token_stream())?;
+    let fmt = LitStr::new(&rewrite_format(&fmt.value(), hidden), fmt.span());
+
+    let args = rewrite_args(tokens.collect(), hidden);
+
+    Ok(quote!(#fmt #args))
+}
+
+/// Replace `{member}` and `{member:spec}` with the placeholder.
+fn rewrite_format(fmt: &str, hidden: &HashSet<String>) -> String {
+    let mut result = String::with_capacity(fmt.len());
+    let mut chars = fmt.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                result.push_str("{{");
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                result.push_str("}}");
+            }
+            '{' => {
+                let mut inner = String::new();
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        break;
+                    }
+                    inner.push(c);
+                }
+
+                let arg = inner.split(':').next().unwrap_or_default().trim();
+                if hidden.contains(arg) {
+                    result.push_str(PLACEHOLDER);
+                } else {
+                    result.push('{');
+                    result.push_str(&inner);
+                    result.push('}');
+                }
+            }
+            c => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Replace additional arguments referencing hidden fields (`.member`) with a placeholder.
+fn rewrite_args(tokens: Vec<TokenTree>, hidden: &HashSet<String>) -> TokenStream {
+    let mut result = Vec::<TokenTree>::with_capacity(tokens.len());
+    let mut tokens = tokens.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        let begin_expr = matches!(result.last(), None | Some(TokenTree::Punct(_)));
+
+        if let TokenTree::Punct(punct) = &token {
+            if punct.as_char() == '.' && begin_expr {
+                let member = match tokens.peek() {
+                    Some(TokenTree::Ident(ident)) => Some(ident.to_string()),
+                    Some(TokenTree::Literal(lit)) => Some(lit.to_string()),
+                    _ => None,
+                };
+                if member.is_some_and(|member| hidden.contains(&member)) {
+                    tokens.next();
+                    result.extend(quote!(::hide::__private::Placeholder));
+                    continue;
+                }
+            }
+        }
+
+        result.push(token);
+    }
+
+    result.into_iter().collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hidden(members: &[&str]) -> HashSet<String> {
+        members.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_rewrite_format() {
+        let hidden = hidden(&["password", "0"]);
+
+        assert_eq!(
+            rewrite_format("failed {user}:{password}", &hidden),
+            "failed {user}:***"
+        );
+        assert_eq!(rewrite_format("{password:?} {0:>8}", &hidden), "*** ***");
+        assert_eq!(
+            rewrite_format("{{password}} {}", &hidden),
+            "{{password}} {}"
+        );
+    }
+}
@@ -0,0 +1,80 @@
+//! Encryption of hidden values, using `age`
+//!
+//! ```rust
+//! use age::x25519::Identity;
+//! use hide::Hide;
+//!
+//! let identity = Identity::generate();
+//! let recipient = identity.to_public();
+//!
+//! let key = Hide::new(b"secret".to_vec());
+//! let ciphertext = key.encrypt_to([&recipient as _]).unwrap();
+//!
+//! let decrypted = Hide::decrypt_with(&identity, &ciphertext).unwrap();
+//! assert_eq!(decrypted, key);
+//! ```
+
+use crate::Hide;
+use ::age::{DecryptError, Decryptor, EncryptError, Encryptor, Identity, Recipient};
+use std::io::{Read, Write};
+
+impl Hide<Vec<u8>> {
+    /// Encrypt the value to all recipients.
+    pub fn encrypt_to<'a, I>(&self, recipients: I) -> Result<Vec<u8>, EncryptError>
+    where
+        I: IntoIterator<Item = &'a dyn Recipient>,
+    {
+        let encryptor = Encryptor::with_recipients(recipients.into_iter())?;
+
+        let mut ciphertext = Vec::new();
+        let mut writer = encryptor.wrap_output(&mut ciphertext)?;
+        writer.write_all(&self.0)?;
+        writer.finish()?;
+
+        Ok(ciphertext)
+    }
+
+    /// Decrypt a value, which was encrypted to the identity.
+    pub fn decrypt_with(identity: &dyn Identity, ciphertext: &[u8]) -> Result<Self, DecryptError> {
+        let decryptor = Decryptor::new_buffered(ciphertext)?;
+        let mut reader = decryptor.decrypt(std::iter::once(identity))?;
+
+        // the plaintext is shorter than the ciphertext, so that this never needs to reallocate,
+        // leaving copies behind
+        let mut plaintext = Vec::with_capacity(ciphertext.len());
+        reader.read_to_end(&mut plaintext)?;
+
+        Ok(Self(plaintext))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::age::x25519;
+
+    #[test]
+    fn test_roundtrip() {
+        let alice = x25519::Identity::generate();
+        let bob = x25519::Identity::generate();
+        let eve = x25519::Identity::generate();
+
+        let key = Hide(b"secret".to_vec());
+        let ciphertext = key
+            .encrypt_to([&alice.to_public() as _, &bob.to_public() as _])
+            .unwrap();
+
+        assert_eq!(Hide::decrypt_with(&alice, &ciphertext).unwrap(), key);
+        assert_eq!(Hide::decrypt_with(&bob, &ciphertext).unwrap(), key);
+        assert!(Hide::decrypt_with(&eve, &ciphertext).is_err());
+    }
+
+    #[test]
+    fn test_no_recipients() {
+        let key = Hide(b"secret".to_vec());
+        assert!(matches!(
+            key.encrypt_to([]),
+            Err(EncryptError::MissingRecipients)
+        ));
+    }
+}
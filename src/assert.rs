@@ -0,0 +1,100 @@
+use std::fmt::Arguments;
+
+/// Assert that two secrets are equal, without showing them if they are not.
+///
+/// On failure, only the lengths of both values and the position of the first difference are
+/// reported. Both sides must dereference to something which can be viewed as bytes, like
+/// `Hide<String>`, `Hide<Vec<u8>>`, `String`, or `&str`.
+///
+/// ```rust
+/// use hide::{assert_secret_eq, Hide};
+///
+/// let token = Hide::new("secret".to_string());
+/// assert_secret_eq!(token, "secret");
+///
+/// let result = std::panic::catch_unwind(|| assert_secret_eq!(token, "secrets"));
+/// let message = *result.unwrap_err().downcast::<String>().unwrap();
+/// assert!(!message.contains("secret"));
+/// ```
+#[macro_export]
+macro_rules! assert_secret_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => $crate::__private::assert_secret_eq(
+                ::core::convert::AsRef::<[u8]>::as_ref(&**left),
+                ::core::convert::AsRef::<[u8]>::as_ref(&**right),
+                ::core::option::Option::None,
+            ),
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (left, right) => $crate::__private::assert_secret_eq(
+                ::core::convert::AsRef::<[u8]>::as_ref(&**left),
+                ::core::convert::AsRef::<[u8]>::as_ref(&**right),
+                ::core::option::Option::Some(::core::format_args!($($arg)+)),
+            ),
+        }
+    };
+}
+
+#[track_caller]
+pub fn assert_secret_eq(left: &[u8], right: &[u8], message: Option<Arguments<'_>>) {
+    if left == right {
+        return;
+    }
+
+    let position = left
+        .iter()
+        .zip(right)
+        .position(|(l, r)| l != r)
+        .unwrap_or(left.len().min(right.len()));
+
+    let message = message.map(|message| format!(": {message}"));
+    panic!(
+        r#"assertion `left == right` failed{}
+  left: {} bytes
+ right: {} bytes
+ first difference at byte {position}"#,
+        message.as_deref().unwrap_or_default(),
+        left.len(),
+        right.len(),
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Hide;
+
+    fn failure(f: impl FnOnce() + std::panic::UnwindSafe) -> String {
+        *std::panic::catch_unwind(f)
+            .unwrap_err()
+            .downcast::<String>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_equal() {
+        assert_secret_eq!(Hide("foo".to_string()), Hide("foo".to_string()));
+        assert_secret_eq!(Hide(vec![1u8, 2]), vec![1u8, 2]);
+        assert_secret_eq!("foo".to_string(), "foo");
+    }
+
+    #[test]
+    fn test_different() {
+        assert_eq!(
+            failure(|| assert_secret_eq!(Hide("secret"), Hide("secure"))),
+            r#"assertion `left == right` failed
+  left: 6 bytes
+ right: 6 bytes
+ first difference at byte 3"#
+        );
+        assert_eq!(
+            failure(|| assert_secret_eq!(Hide("secret"), "secretive", "token of {}", "alice")),
+            r#"assertion `left == right` failed: token of alice
+  left: 6 bytes
+ right: 9 bytes
+ first difference at byte 6"#
+        );
+    }
+}
@@ -0,0 +1,250 @@
+//! An audit trail of exposed secrets
+//!
+//! Hidden values can always be accessed through [`Deref`](std::ops::Deref) or the public field.
+//! Accessing them through [`Hide::expose`] instead records the exposure, with a label, the
+//! location of the caller, and a timestamp, to the installed [`Sink`]:
+//!
+//! ```rust
+//! use hide::{audit, Hide};
+//!
+//! audit::install(|exposure: &audit::Exposure<'_>| {
+//!     eprintln!("exposed {} at {}", exposure.label, exposure.location);
+//! });
+//!
+//! let password = Hide::new("secret".to_string());
+//! let url = format!("postgres://admin:{}@localhost", *password.expose("db_password"));
+//! ```
+//!
+//! Nothing is recorded, unless a sink is installed. Sinks for writing to a file
+//! ([`WriterSink`]), or for emitting `tracing` events, are available.
+
+use crate::{Exposed, ExposedMut, Hide, Labeled};
+use std::fmt::{Debug, Formatter};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::panic::Location;
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static SINK: RwLock<Option<Arc<dyn Sink>>> = RwLock::new(None);
+
+/// An exposure of a hidden value.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct Exposure<'a> {
+    /// The label of the exposed value.
+    pub label: &'a str,
+    /// The location of the code exposing the value.
+    pub location: &'static Location<'static>,
+    /// The time of the exposure.
+    pub timestamp: SystemTime,
+}
+
+/// A destination for exposures.
+///
+/// This is implemented for closures, taking an [`Exposure`].
+pub trait Sink: Send + Sync {
+    fn record(&self, exposure: &Exposure<'_>);
+}
+
+impl<F> Sink for F
+where
+    F: Fn(&Exposure<'_>) + Send + Sync,
+{
+    fn record(&self, exposure: &Exposure<'_>) {
+        self(exposure)
+    }
+}
+
+/// Install the sink, replacing the previous one.
+pub fn install(sink: impl Sink + 'static) {
+    *SINK.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(sink));
+}
+
+/// Remove the sink, so that exposures are no longer recorded.
+pub fn uninstall() {
+    *SINK.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// Check if a sink is installed.
+pub fn is_enabled() -> bool {
+    SINK.read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .is_some()
+}
+
+/// Record an exposure at the location of the caller.
+///
+/// This is used by [`Hide::expose`], and can be used by custom code accessing secrets, e.g. in
+/// other wrapper types.
+#[track_caller]
+pub fn record(label: &str) {
+    let location = Location::caller();
+
+    // don't hold the lock while recording, the sink may expose values as well
+    let Some(sink) = SINK.read().unwrap_or_else(PoisonError::into_inner).clone() else {
+        return;
+    };
+
+    sink.record(&Exposure {
+        label,
+        location,
+        timestamp: SystemTime::now(),
+    });
+}
+
+impl<T> Hide<T> {
+    /// Access the value, recording the exposure, see [`audit`](crate::audit).
+    #[track_caller]
+    pub fn expose(&self, label: &str) -> Exposed<'_, T> {
+        record(label);
+        Exposed::new(&self.0)
+    }
+
+    /// Mutably access the value, recording the exposure, see [`audit`](crate::audit).
+    #[track_caller]
+    pub fn expose_mut(&mut self, label: &str) -> ExposedMut<'_, T> {
+        record(label);
+        ExposedMut::new(&mut self.0)
+    }
+}
+
+impl<T> Labeled<T> {
+    /// Access the value, recording the exposure with the label of the value.
+    #[track_caller]
+    pub fn expose(&self) -> Exposed<'_, T> {
+        record(self.label());
+        Exposed::new(&self.value().0)
+    }
+}
+
+/// A sink, writing one line per exposure.
+///
+/// Lines have the format `<seconds since the epoch> <label> <file>:<line>:<column>`, e.g.
+/// `1700000000.123 db_password src/main.rs:10:5`. Errors writing are ignored.
+pub struct WriterSink<W> {
+    writer: Mutex<W>,
+}
+
+impl<W: Write + Send> WriterSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl WriterSink<File> {
+    /// Append to a file, creating it, if it doesn't exist.
+    pub fn file(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(
+            OpenOptions::new().create(true).append(true).open(path)?,
+        ))
+    }
+}
+
+impl<W> Debug for WriterSink<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WriterSink").finish_non_exhaustive()
+    }
+}
+
+impl<W: Write + Send> Sink for WriterSink<W> {
+    fn record(&self, exposure: &Exposure<'_>) {
+        let timestamp = exposure
+            .timestamp
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        let mut writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        let _ = writeln!(
+            writer,
+            "{}.{:03} {} {}",
+            timestamp.as_secs(),
+            timestamp.subsec_millis(),
+            exposure.label,
+            exposure.location
+        )
+        .and_then(|()| writer.flush());
+    }
+}
+
+/// A sink, emitting `tracing` events at the info level, with the target `hide::audit`.
+///
+/// The events have the fields `label` and `location`.
+#[cfg(feature = "tracing")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TracingSink;
+
+#[cfg(feature = "tracing")]
+impl Sink for TracingSink {
+    fn record(&self, exposure: &Exposure<'_>) {
+        ::tracing::info!(
+            target: "hide::audit",
+            label = exposure.label,
+            location = %exposure.location,
+            "exposed hidden value"
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let exposures = Arc::new(Mutex::new(Vec::new()));
+        let recorded = exposures.clone();
+        install(move |exposure: &Exposure<'_>| {
+            recorded
+                .lock()
+                .unwrap()
+                .push((exposure.label.to_string(), exposure.location.line()));
+        });
+        assert!(is_enabled());
+
+        let mut password = Hide::new("secret".to_string());
+        let line = line!() + 1;
+        assert_eq!(*password.expose("password"), "secret");
+        password.expose_mut("password").push('!');
+        let token = Hide::<String>::labeled("token", "foo");
+        assert_eq!(*token.expose(), "foo");
+
+        uninstall();
+        password.expose("password");
+
+        let exposures = exposures.lock().unwrap();
+        assert_eq!(
+            *exposures,
+            [
+                ("password".to_string(), line),
+                ("password".to_string(), line + 1),
+                ("token".to_string(), line + 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_writer_sink() {
+        let sink = WriterSink::new(Vec::new());
+        let location = Location::caller();
+        sink.record(&Exposure {
+            label: "db_password",
+            location,
+            timestamp: UNIX_EPOCH + std::time::Duration::from_millis(1_700_000_000_123),
+        });
+
+        assert_eq!(
+            String::from_utf8(sink.into_inner()).unwrap(),
+            format!("1700000000.123 db_password {location}\n")
+        );
+    }
+}
@@ -0,0 +1,168 @@
+//! Integration with `clap`
+//!
+//! Arguments of type [`Hide<T>`] are parsed using [`HideValueParser`], which never includes the
+//! value in errors. However, clap still shows default values and values taken from the
+//! environment in the help output. [`hidden`] replaces those with `***`:
+//!
+//! ```rust
+//! use clap::Parser;
+//! use hide::Hide;
+//!
+//! #[derive(Parser)]
+//! #[command(mut_arg("password", hide::clap::hidden))]
+//! struct Cli {
+//!     #[arg(long, env = "DB_PASSWORD", default_value = "changeme")]
+//!     password: Hide<String>,
+//! }
+//!
+//! # use clap::CommandFactory;
+//! let help = Cli::command().render_help().to_string();
+//! assert!(help.contains("[env: DB_PASSWORD=***]"));
+//! assert!(help.contains("[default: ***]"));
+//! assert!(!help.contains("changeme"));
+//! ```
+
+use crate::{Hide, SUBSTITUTE};
+use ::clap::builder::{TypedValueParser, ValueParserFactory};
+use ::clap::error::{ContextKind, ContextValue, ErrorKind};
+use ::clap::{Arg, Command, Error};
+use std::ffi::OsStr;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// Parse a hidden value, using [`FromStr`] of the inner type.
+///
+/// Errors neither contain the value, nor the error of the inner type, as it might echo the value.
+pub struct HideValueParser<T>(PhantomData<fn() -> T>);
+
+impl<T> HideValueParser<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for HideValueParser<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for HideValueParser<T> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<T> TypedValueParser for HideValueParser<T>
+where
+    T: FromStr + Clone + Send + Sync + 'static,
+{
+    type Value = Hide<T>;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, Error> {
+        value
+            .to_str()
+            .and_then(|value| value.parse().ok())
+            .map(Hide)
+            .ok_or_else(|| invalid_value(cmd, arg))
+    }
+}
+
+fn invalid_value(cmd: &Command, arg: Option<&Arg>) -> Error {
+    let arg = arg.map_or_else(|| "...".to_string(), ToString::to_string);
+
+    let mut err = Error::new(ErrorKind::ValueValidation).with_cmd(cmd);
+    err.insert(ContextKind::InvalidArg, ContextValue::String(arg));
+    err.insert(
+        ContextKind::InvalidValue,
+        ContextValue::String(SUBSTITUTE.to_string()),
+    );
+    err
+}
+
+impl<T> ValueParserFactory for Hide<T>
+where
+    T: FromStr + Clone + Send + Sync + 'static,
+{
+    type Parser = HideValueParser<T>;
+
+    fn value_parser() -> Self::Parser {
+        HideValueParser::new()
+    }
+}
+
+/// Hide the default value and the value of the environment variable in the help output.
+///
+/// This can be used with [`Command::mut_arg`] and [`Command::mut_args`].
+pub fn hidden(mut arg: Arg) -> Arg {
+    let mut spec = Vec::new();
+
+    if let Some(env) = arg.get_env() {
+        spec.push(format!("[env: {}={SUBSTITUTE}]", env.to_string_lossy()));
+        arg = arg.hide_env(true);
+    }
+    if !arg.get_default_values().is_empty() {
+        spec.push(format!("[default: {SUBSTITUTE}]"));
+        arg = arg.hide_default_value(true);
+    }
+
+    if spec.is_empty() {
+        return arg;
+    }
+
+    let spec = spec.join(" ");
+    let help = match arg.get_help() {
+        Some(help) => format!("{help} {spec}"),
+        None => spec.clone(),
+    };
+    let long_help = arg.get_long_help().map(|help| format!("{help}\n\n{spec}"));
+
+    arg = arg.help(help);
+    if let Some(long_help) = long_help {
+        arg = arg.long_help(long_help);
+    }
+    arg
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::clap::{CommandFactory, Parser};
+
+    #[derive(Debug, Parser)]
+    #[command(name = "test", mut_arg("password", hidden))]
+    struct Cli {
+        /// The password
+        #[arg(long, env = "HIDE_TEST_PASSWORD", default_value = "changeme")]
+        password: Hide<String>,
+        #[arg(long)]
+        pin: Option<Hide<u32>>,
+    }
+
+    #[test]
+    fn test_parse() {
+        let cli = Cli::try_parse_from(["test", "--password", "foo", "--pin", "1234"]).unwrap();
+        assert_eq!(cli.password.0, "foo");
+        assert_eq!(cli.pin.map(|pin| pin.0), Some(1234));
+    }
+
+    #[test]
+    fn test_help() {
+        let help = Cli::command().render_help().to_string();
+        assert!(help.contains("The password [env: HIDE_TEST_PASSWORD=***] [default: ***]"));
+        assert!(!help.contains("changeme"));
+    }
+
+    #[test]
+    fn test_error() {
+        let err = Cli::try_parse_from(["test", "--pin", "12x4"]).unwrap_err();
+        let err = err.render().to_string();
+        assert!(err.starts_with("error: invalid value '***' for '--pin <PIN>'"));
+        assert!(!err.contains("12x4"));
+    }
+}
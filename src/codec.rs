@@ -0,0 +1,258 @@
+//! Encoding conversions between hidden strings and hidden bytes
+//!
+//! All conversions write directly into a buffer of the correct size, so that no unprotected
+//! intermediate copy is created.
+//!
+//! ```rust
+//! use hide::{Hide, codec};
+//!
+//! let key = Hide::new(vec![0xde, 0xad, 0xbe, 0xef]);
+//!
+//! let hex = codec::encode_hex(&key);
+//! assert_eq!(hex.as_str(), "deadbeef");
+//! assert_eq!(codec::decode_hex(&hex).unwrap(), key);
+//!
+//! let base64 = codec::encode_base64(&key);
+//! assert_eq!(base64.as_str(), "3q2+7w==");
+//! assert_eq!(codec::decode_base64(&base64).unwrap(), key);
+//! ```
+
+use crate::builder::wipe_bytes;
+use crate::{Hide, HideBuilder};
+use std::fmt::{Display, Formatter};
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+pub(crate) const BASE64: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE32: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Failed to decode a hidden value.
+///
+/// This never contains any part of the input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The input has an invalid length.
+    InvalidLength,
+    /// The input contains a character which is not part of the encoding.
+    InvalidCharacter,
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidLength => f.write_str("invalid length of encoded value"),
+            Self::InvalidCharacter => f.write_str("invalid character in encoded value"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Encode as lowercase hex.
+pub fn encode_hex<T: AsRef<[u8]>>(value: &Hide<T>) -> Hide<String> {
+    let value = value.0.as_ref();
+    let mut builder = HideBuilder::with_capacity(value.len() * 2);
+    for b in value {
+        builder = builder
+            .push(HEX[(b >> 4) as usize] as char)
+            .push(HEX[(b & 0xf) as usize] as char);
+    }
+    builder.build()
+}
+
+/// Decode hex, accepting upper and lowercase characters.
+pub fn decode_hex<T: AsRef<str>>(value: &Hide<T>) -> Result<Hide<Vec<u8>>, DecodeError> {
+    decode_hex_str(value.0.as_ref()).map(Hide)
+}
+
+pub(crate) fn decode_hex_str(value: &str) -> Result<Vec<u8>, DecodeError> {
+    let value = value.as_bytes();
+    if value.len() % 2 != 0 {
+        return Err(DecodeError::InvalidLength);
+    }
+
+    let mut result = Vec::with_capacity(value.len() / 2);
+    for pair in value.chunks(2) {
+        let hi = (pair[0] as char).to_digit(16);
+        let lo = (pair[1] as char).to_digit(16);
+        match (hi, lo) {
+            (Some(hi), Some(lo)) => result.push((hi << 4 | lo) as u8),
+            _ => {
+                wipe_bytes(&mut result);
+                return Err(DecodeError::InvalidCharacter);
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Encode as (standard, padded) base64.
+pub fn encode_base64<T: AsRef<[u8]>>(value: &Hide<T>) -> Hide<String> {
+    HideBuilder::new().push_base64(value.0.as_ref()).build()
+}
+
+/// Decode (standard) base64, padding is optional.
+pub fn decode_base64<T: AsRef<str>>(value: &Hide<T>) -> Result<Hide<Vec<u8>>, DecodeError> {
+    let value = value.0.as_ref().as_bytes();
+    let value = match value.len() % 4 {
+        0 => value
+            .strip_suffix(b"==")
+            .or_else(|| value.strip_suffix(b"="))
+            .unwrap_or(value),
+        1 => return Err(DecodeError::InvalidLength),
+        _ => value,
+    };
+    if value.len() % 4 == 1 {
+        return Err(DecodeError::InvalidLength);
+    }
+
+    let mut result = Vec::with_capacity(value.len() * 3 / 4);
+    for chunk in value.chunks(4) {
+        let mut n = 0u32;
+        for (i, c) in chunk.iter().enumerate() {
+            match BASE64.iter().position(|b| b == c) {
+                Some(v) => n |= (v as u32) << (18 - i * 6),
+                None => {
+                    wipe_bytes(&mut result);
+                    return Err(DecodeError::InvalidCharacter);
+                }
+            }
+        }
+        for i in 0..chunk.len() - 1 {
+            result.push((n >> (16 - i * 8)) as u8);
+        }
+    }
+
+    Ok(Hide(result))
+}
+
+/// Encode as (standard, padded) base32.
+pub fn encode_base32<T: AsRef<[u8]>>(value: &Hide<T>) -> Hide<String> {
+    let value = value.0.as_ref();
+    let mut builder = HideBuilder::with_capacity(value.len().div_ceil(5) * 8);
+
+    for chunk in value.chunks(5) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u64, |n, (i, b)| n | (*b as u64) << (32 - i * 8));
+        // number of characters carrying data, the rest is padding
+        let chars = (chunk.len() * 8).div_ceil(5);
+
+        for i in 0..8 {
+            builder = match i < chars {
+                true => builder.push(BASE32[(n >> (35 - i * 5)) as usize & 0x1f] as char),
+                false => builder.push('='),
+            };
+        }
+    }
+
+    builder.build()
+}
+
+/// Decode (standard) base32, accepting upper and lowercase characters, padding is optional.
+pub fn decode_base32<T: AsRef<str>>(value: &Hide<T>) -> Result<Hide<Vec<u8>>, DecodeError> {
+    let value = value.0.as_ref().as_bytes();
+    let value = match value.len() % 8 {
+        0 => &value[..value.iter().rposition(|c| *c != b'=').map_or(0, |i| i + 1)],
+        _ => value,
+    };
+    // the number of characters in the last chunk must be able to carry full bytes
+    if matches!(value.len() % 8, 1 | 3 | 6) {
+        return Err(DecodeError::InvalidLength);
+    }
+
+    let mut result = Vec::with_capacity(value.len() * 5 / 8);
+    for chunk in value.chunks(8) {
+        let mut n = 0u64;
+        for (i, c) in chunk.iter().enumerate() {
+            match BASE32.iter().position(|b| b.eq_ignore_ascii_case(c)) {
+                Some(v) => n |= (v as u64) << (35 - i * 5),
+                None => {
+                    wipe_bytes(&mut result);
+                    return Err(DecodeError::InvalidCharacter);
+                }
+            }
+        }
+        for i in 0..chunk.len() * 5 / 8 {
+            result.push((n >> (32 - i * 8)) as u8);
+        }
+    }
+
+    Ok(Hide(result))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hide(value: &str) -> Hide<&str> {
+        Hide(value)
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(encode_hex(&Hide(b"\x00\x0f\xff")).as_str(), "000fff");
+        assert_eq!(decode_hex(&hide("000FfF")).unwrap().0, b"\x00\x0f\xff");
+        assert_eq!(decode_hex(&hide("0")), Err(DecodeError::InvalidLength));
+        assert_eq!(decode_hex(&hide("zz")), Err(DecodeError::InvalidCharacter));
+    }
+
+    #[test]
+    fn test_base64() {
+        for (input, expected) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(encode_base64(&Hide(input)).as_str(), expected);
+            assert_eq!(decode_base64(&hide(expected)).unwrap().0, input.as_bytes());
+            assert_eq!(
+                decode_base64(&hide(expected.trim_end_matches('=')))
+                    .unwrap()
+                    .0,
+                input.as_bytes()
+            );
+        }
+
+        assert_eq!(
+            decode_base64(&hide("Zm9vY")),
+            Err(DecodeError::InvalidLength)
+        );
+        assert_eq!(
+            decode_base64(&hide("Zm9v!A==")),
+            Err(DecodeError::InvalidCharacter)
+        );
+    }
+
+    #[test]
+    fn test_base32() {
+        for (input, expected) in [
+            ("", ""),
+            ("f", "MY======"),
+            ("fo", "MZXQ===="),
+            ("foo", "MZXW6==="),
+            ("foob", "MZXW6YQ="),
+            ("fooba", "MZXW6YTB"),
+            ("foobar", "MZXW6YTBOI======"),
+        ] {
+            assert_eq!(encode_base32(&Hide(input)).as_str(), expected);
+            assert_eq!(decode_base32(&hide(expected)).unwrap().0, input.as_bytes());
+            assert_eq!(
+                decode_base32(&hide(&expected.trim_end_matches('=').to_lowercase()))
+                    .unwrap()
+                    .0,
+                input.as_bytes()
+            );
+        }
+
+        assert_eq!(decode_base32(&hide("MZX")), Err(DecodeError::InvalidLength));
+        assert_eq!(
+            decode_base32(&hide("MZXW6YT1")),
+            Err(DecodeError::InvalidCharacter)
+        );
+    }
+}
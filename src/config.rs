@@ -0,0 +1,195 @@
+//! Integration with `config`
+//!
+//! [`Hide`] can be read from a [`Config`] like any other value, either using [`Config::get`] or
+//! [`Hide::from_config`]. However, the [`Debug`] output of a [`Config`] contains the full
+//! configuration tree, including all secrets. [`Denylist::snapshot`] creates a copy of the tree,
+//! masking all keys matching the denylist:
+//!
+//! ```rust
+//! use config::Config;
+//! use hide::{Hide, config::Denylist};
+//!
+//! let config = Config::builder()
+//!     .set_override("database.user", "admin")?
+//!     .set_override("database.password", Hide::new("hunter2"))?
+//!     .build()?;
+//!
+//! let password: Hide<String> = config.get("database.password")?;
+//! let snapshot = Denylist::default().snapshot(&config)?;
+//!
+//! assert!(!format!("{snapshot:?}").contains("hunter2"));
+//! # Ok::<(), config::ConfigError>(())
+//! ```
+
+use crate::{Hide, SUBSTITUTE};
+use ::config::{Config, ConfigError, Map, Value, ValueKind};
+use serde::de::DeserializeOwned;
+use std::borrow::Cow;
+use std::fmt::{Debug, Formatter};
+
+impl<T> Hide<T>
+where
+    T: DeserializeOwned,
+{
+    /// Convert a configuration value into a hidden value.
+    pub fn from_config(value: Value) -> Result<Self, ConfigError> {
+        value.try_deserialize().map(Self)
+    }
+}
+
+impl<T> From<Hide<T>> for ValueKind
+where
+    T: Into<ValueKind>,
+{
+    fn from(value: Hide<T>) -> Self {
+        value.0.into()
+    }
+}
+
+/// Names of keys which must be masked.
+///
+/// A key matches if any of its segments contains one of the names, ignoring case. So that
+/// `password` matches `database.password` as well as `smtp.user_password.file`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Denylist {
+    names: Vec<Cow<'static, str>>,
+}
+
+impl Default for Denylist {
+    /// Create a denylist of common names for secrets.
+    fn default() -> Self {
+        Self::new([
+            "password",
+            "passwd",
+            "secret",
+            "token",
+            "credential",
+            "api_key",
+            "apikey",
+            "private_key",
+        ])
+    }
+}
+
+impl Denylist {
+    pub fn new<I>(names: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Cow<'static, str>>,
+    {
+        Self {
+            names: names.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Add a name.
+    pub fn name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.names.push(name.into());
+        self
+    }
+
+    /// Check if a (dotted) key is denied.
+    pub fn is_denied(&self, key: &str) -> bool {
+        key.split('.')
+            .any(|segment| crate::sensitive(&self.names, segment))
+    }
+
+    /// Create a snapshot of the configuration, masking all denied keys.
+    pub fn snapshot(&self, config: &Config) -> Result<Snapshot, ConfigError> {
+        let mut table = config.cache.clone().into_table()?;
+        self.redact_table(&mut table);
+        Ok(Snapshot(table))
+    }
+
+    fn redact_table(&self, table: &mut Map<String, Value>) {
+        for (key, value) in table.iter_mut() {
+            if self.is_denied(key) {
+                let origin = value.origin().map(ToString::to_string);
+                *value = Value::new(origin.as_ref(), SUBSTITUTE);
+            } else {
+                self.redact_value(value);
+            }
+        }
+    }
+
+    fn redact_value(&self, value: &mut Value) {
+        match &mut value.kind {
+            ValueKind::Table(table) => self.redact_table(table),
+            ValueKind::Array(values) => values.iter_mut().for_each(|v| self.redact_value(v)),
+            _ => {}
+        }
+    }
+}
+
+/// A copy of the configuration tree, with all denied keys being masked.
+///
+/// Created by [`Denylist::snapshot`].
+#[derive(Clone, PartialEq)]
+pub struct Snapshot(Map<String, Value>);
+
+impl Snapshot {
+    pub fn as_map(&self) -> &Map<String, Value> {
+        &self.0
+    }
+}
+
+impl Debug for Snapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn config() -> Config {
+        Config::builder()
+            .set_override("database.user", "admin")
+            .unwrap()
+            .set_override("database.password", Hide::new("hunter2"))
+            .unwrap()
+            .set_override("servers[0].api_key", "foo")
+            .unwrap()
+            .set_override("Secret", "bar")
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_from_config() {
+        let config = config();
+
+        let value = config.get::<Value>("database.password").unwrap();
+        let password = Hide::<String>::from_config(value).unwrap();
+        assert_eq!(password.0, "hunter2");
+
+        let password: Hide<String> = config.get("database.password").unwrap();
+        assert_eq!(password.0, "hunter2");
+    }
+
+    #[test]
+    fn test_denylist() {
+        let denylist = Denylist::new(["password"]).name("Token");
+
+        assert!(denylist.is_denied("database.password"));
+        assert!(denylist.is_denied("smtp.user_password.file"));
+        assert!(denylist.is_denied("auth.TOKEN"));
+        assert!(!denylist.is_denied("database.user"));
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let snapshot = Denylist::default().snapshot(&config()).unwrap();
+        let debug = format!("{snapshot:?}");
+
+        assert!(debug.contains("admin"));
+        assert!(!debug.contains("hunter2"));
+        assert!(!debug.contains("foo"));
+        assert!(!debug.contains("bar"));
+
+        let database = snapshot.as_map()["database"].clone().into_table().unwrap();
+        assert_eq!(database["password"].clone().into_string().unwrap(), "***");
+    }
+}
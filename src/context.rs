@@ -0,0 +1,141 @@
+//! Attach hidden values as context to errors
+//!
+//! Other than attaching the secret itself, which would end up in the error report, this only
+//! attaches [`Redacted`] values, like [`Hide`] or [`Labeled`]. So that the report can show
+//! which secret was involved, but not its value:
+//!
+//! ```rust
+//! # #[cfg(feature = "anyhow")]
+//! # {
+//! use hide::{Hide, context::HiddenContext};
+//!
+//! fn connect(password: &str) -> std::io::Result<()> {
+//!     Err(std::io::ErrorKind::PermissionDenied.into())
+//! }
+//!
+//! let password = Hide::<String>::labeled("db_password", "secret");
+//! let err = connect(&password)
+//!     .context_hidden(password.clone())
+//!     .unwrap_err();
+//!
+//! assert_eq!(format!("{err:#}"), "<db_password:***>: permission denied");
+//! # }
+//! ```
+
+use crate::{Hide, HideIn, HideOnce, Labeled};
+use std::fmt::{Debug, Display};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A value which never shows its content in the [`Display`] and [`Debug`] output.
+///
+/// This is implemented for the types of this crate only.
+pub trait Redacted: Display + Debug + Send + Sync + 'static + sealed::Sealed {}
+
+macro_rules! redacted {
+    ($($ty:ident),*) => {
+        $(
+            impl<T> sealed::Sealed for $ty<T> {}
+            impl<T> Redacted for $ty<T> where Self: Display + Debug + Send + Sync + 'static {}
+        )*
+    };
+}
+
+redacted!(Hide, HideIn, HideOnce, Labeled);
+
+impl<C> sealed::Sealed for crate::pii::Pii<C> {}
+impl<C> Redacted for crate::pii::Pii<C> where Self: Display + Debug + Send + Sync + 'static {}
+
+impl sealed::Sealed for crate::Password {}
+#[cfg(not(feature = "paranoid"))]
+impl Redacted for crate::Password {}
+impl sealed::Sealed for crate::ApiKey {}
+#[cfg(not(feature = "paranoid"))]
+impl Redacted for crate::ApiKey {}
+impl sealed::Sealed for crate::BearerToken {}
+#[cfg(not(feature = "paranoid"))]
+impl Redacted for crate::BearerToken {}
+
+/// Extension for [`anyhow::Context`], attaching only [`Redacted`] values.
+#[cfg(feature = "anyhow")]
+pub trait HiddenContext<T, E>: anyhow::Context<T, E> + Sized {
+    /// Wrap the error with context, which is a redacted value.
+    fn context_hidden<C>(self, context: C) -> anyhow::Result<T>
+    where
+        C: Redacted,
+    {
+        self.context(context)
+    }
+
+    /// Wrap the error with context, which is a redacted value, lazily evaluated.
+    fn with_hidden_context<C, F>(self, f: F) -> anyhow::Result<T>
+    where
+        C: Redacted,
+        F: FnOnce() -> C,
+    {
+        self.with_context(f)
+    }
+}
+
+#[cfg(feature = "anyhow")]
+impl<R, T, E> HiddenContext<T, E> for R where R: anyhow::Context<T, E> {}
+
+/// Extension for [`eyre::WrapErr`], attaching only [`Redacted`] values.
+#[cfg(feature = "eyre")]
+pub trait HiddenWrapErr<T, E>: eyre::WrapErr<T, E> + Sized {
+    /// Wrap the error with a message, which is a redacted value.
+    fn wrap_err_hidden<C>(self, msg: C) -> eyre::Result<T>
+    where
+        C: Redacted,
+    {
+        self.wrap_err(msg)
+    }
+
+    /// Wrap the error with a message, which is a redacted value, lazily evaluated.
+    fn wrap_err_with_hidden<C, F>(self, f: F) -> eyre::Result<T>
+    where
+        C: Redacted,
+        F: FnOnce() -> C,
+    {
+        self.wrap_err_with(f)
+    }
+}
+
+#[cfg(feature = "eyre")]
+impl<R, T, E> HiddenWrapErr<T, E> for R where R: eyre::WrapErr<T, E> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn fail() -> std::io::Result<()> {
+        Err(std::io::Error::other("boom"))
+    }
+
+    #[test]
+    #[cfg(feature = "anyhow")]
+    fn test_anyhow() {
+        let token = Hide::<String>::labeled("api_token", "secret");
+
+        let err = fail().context_hidden(token.clone()).unwrap_err();
+        assert_eq!(format!("{err:#}"), "<api_token:***>: boom");
+
+        let err = None::<()>
+            .with_hidden_context(|| token.clone())
+            .unwrap_err();
+        assert_eq!(format!("{err:#}"), "<api_token:***>");
+        assert!(!format!("{err:?}").contains("secret"));
+    }
+
+    #[test]
+    #[cfg(feature = "eyre")]
+    fn test_eyre() {
+        let err = fail()
+            .wrap_err_with_hidden(|| Hide("secret".to_string()))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "***");
+        assert!(!format!("{err:?}").contains("secret"));
+    }
+}
@@ -0,0 +1,134 @@
+//! Integration with `cookie`
+
+use crate::SUBSTITUTE;
+use ::cookie::{Cookie, CookieBuilder};
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Deref, DerefMut};
+
+/// A cookie, with its value hidden, like session IDs.
+///
+/// The [`Debug`] output shows the name and the attributes, while [`Display`] renders the
+/// `Set-Cookie` header value, both using `***` for the value:
+///
+/// ```rust
+/// use cookie::Cookie;
+/// use hide::HiddenCookie;
+///
+/// let cookie = HiddenCookie::from(Cookie::build(("session", "abc123")).path("/").secure(true));
+///
+/// assert_eq!(cookie.to_string(), "session=***; Secure; Path=/");
+/// assert_eq!(
+///     format!("{cookie:?}"),
+///     r#"HiddenCookie { name: "session", value: ***, secure: Some(true), path: Some("/") }"#
+/// );
+/// assert_eq!(cookie.value(), "abc123");
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct HiddenCookie<'c>(pub Cookie<'c>);
+
+impl<'c> HiddenCookie<'c> {
+    pub fn new(cookie: Cookie<'c>) -> Self {
+        Self(cookie)
+    }
+
+    pub fn into_inner(self) -> Cookie<'c> {
+        self.0
+    }
+
+    /// Convert into a cookie, which doesn't borrow anything.
+    pub fn into_owned(self) -> HiddenCookie<'static> {
+        HiddenCookie(self.0.into_owned())
+    }
+}
+
+impl<'c> Deref for HiddenCookie<'c> {
+    type Target = Cookie<'c>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for HiddenCookie<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<'c> From<Cookie<'c>> for HiddenCookie<'c> {
+    fn from(value: Cookie<'c>) -> Self {
+        Self(value)
+    }
+}
+
+impl<'c> From<CookieBuilder<'c>> for HiddenCookie<'c> {
+    fn from(value: CookieBuilder<'c>) -> Self {
+        Self(value.build())
+    }
+}
+
+impl<'c> From<HiddenCookie<'c>> for Cookie<'c> {
+    fn from(value: HiddenCookie<'c>) -> Self {
+        value.0
+    }
+}
+
+impl Debug for HiddenCookie<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut debug = f.debug_struct("HiddenCookie");
+        debug
+            .field("name", &self.0.name())
+            .field("value", &format_args!("{SUBSTITUTE}"));
+
+        if let Some(http_only) = self.0.http_only() {
+            debug.field("http_only", &Some(http_only));
+        }
+        if let Some(secure) = self.0.secure() {
+            debug.field("secure", &Some(secure));
+        }
+        if let Some(same_site) = self.0.same_site() {
+            debug.field("same_site", &Some(same_site));
+        }
+        if let Some(max_age) = self.0.max_age() {
+            debug.field("max_age", &Some(max_age));
+        }
+        if let Some(path) = self.0.path() {
+            debug.field("path", &Some(path));
+        }
+        if let Some(domain) = self.0.domain() {
+            debug.field("domain", &Some(domain));
+        }
+        if let Some(expires) = self.0.expires() {
+            debug.field("expires", &Some(expires));
+        }
+        debug.finish()
+    }
+}
+
+impl Display for HiddenCookie<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut cookie = self.0.clone();
+        cookie.set_value(SUBSTITUTE);
+        Display::fmt(&cookie, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_cookie() {
+        let cookie =
+            HiddenCookie::from(Cookie::parse("id=secret; HttpOnly; Domain=example.com").unwrap());
+
+        assert_eq!(cookie.to_string(), "id=***; HttpOnly; Domain=example.com");
+        assert_eq!(
+            format!("{cookie:?}"),
+            r#"HiddenCookie { name: "id", value: ***, http_only: Some(true), domain: Some("example.com") }"#
+        );
+
+        let cookie = Cookie::from(cookie.into_owned());
+        assert_eq!(cookie.value(), "secret");
+    }
+}
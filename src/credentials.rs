@@ -0,0 +1,116 @@
+use crate::{Hide, HideBuilder};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A username and password, e.g. for HTTP basic authentication.
+///
+/// Only the password is hidden:
+///
+/// ```rust
+/// use hide::Credentials;
+///
+/// let credentials: Credentials = "admin:secret".parse().unwrap();
+/// assert_eq!(credentials.username, "admin");
+/// assert_eq!(credentials.password.as_str(), "secret");
+///
+/// assert_eq!(
+///     credentials.to_basic_auth().as_str(),
+///     "Basic YWRtaW46c2VjcmV0"
+/// );
+/// ```
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(not(feature = "paranoid"), derive(Clone))]
+pub struct Credentials {
+    pub username: String,
+    pub password: Hide<String>,
+}
+
+/// Failed to parse [`Credentials`], as the string doesn't contain a `:`.
+///
+/// This never contains any part of the string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseCredentialsError;
+
+impl Display for ParseCredentialsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("credentials must have the format `username:password`")
+    }
+}
+
+impl std::error::Error for ParseCredentialsError {}
+
+impl Credentials {
+    pub fn new(username: impl Into<String>, password: impl Into<Hide<String>>) -> Self {
+        Self {
+            username: username.into(),
+            password: password.into(),
+        }
+    }
+
+    /// The value of an `Authorization` header, using the basic scheme.
+    ///
+    /// This is `Basic `, followed by `username:password` encoded using base64.
+    pub fn to_basic_auth(&self) -> Hide<String> {
+        let credentials =
+            HideBuilder::with_capacity(self.username.len() + self.password.0.len() + 1)
+                .push_str(&self.username)
+                .push(':')
+                .push_str(&self.password.0);
+
+        HideBuilder::new()
+            .push_str("Basic ")
+            .push_base64(&credentials)
+            .build()
+    }
+
+    /// An `Authorization` header value, using the basic scheme, marked as sensitive.
+    #[cfg(feature = "http")]
+    pub fn to_basic_auth_header(&self) -> http::HeaderValue {
+        let mut header = self.to_basic_auth();
+        let mut value =
+            http::HeaderValue::from_str(&header.0).expect("base64 is a valid header value");
+        value.set_sensitive(true);
+        crate::builder::wipe(&mut header.0);
+        value
+    }
+}
+
+/// Parse `username:password`, splitting at the first `:`, so that the password may contain one.
+impl FromStr for Credentials {
+    type Err = ParseCredentialsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (username, password) = s.split_once(':').ok_or(ParseCredentialsError)?;
+        Ok(Self::new(username, password))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let credentials: Credentials = "admin:se:cret".parse().unwrap();
+        assert_eq!(credentials, Credentials::new("admin", "se:cret"));
+        assert_eq!(":".parse(), Ok(Credentials::new("", "")));
+        assert_eq!("secret".parse::<Credentials>(), Err(ParseCredentialsError));
+    }
+
+    #[test]
+    fn test_basic_auth() {
+        let credentials = Credentials::new("user", "secret");
+        assert_eq!(
+            credentials.to_basic_auth().as_str(),
+            "Basic dXNlcjpzZWNyZXQ="
+        );
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_basic_auth_header() {
+        let header = Credentials::new("user", "secret").to_basic_auth_header();
+        assert!(header.is_sensitive());
+        assert_eq!(header, "Basic dXNlcjpzZWNyZXQ=");
+    }
+}
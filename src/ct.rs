@@ -0,0 +1,32 @@
+//! Constant-time operations.
+
+use std::hint::black_box;
+
+/// Compare two byte slices, in constant time for slices of the same length.
+///
+/// The length itself is not considered secret.
+pub(crate) fn eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let diff = a
+        .iter()
+        .zip(b)
+        .fold(0u8, |diff, (a, b)| black_box(diff | (a ^ b)));
+
+    black_box(diff) == 0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_eq() {
+        assert!(eq(b"", b""));
+        assert!(eq(b"foo", b"foo"));
+        assert!(!eq(b"foo", b"bar"));
+        assert!(!eq(b"foo", b"fooo"));
+    }
+}
@@ -0,0 +1,839 @@
+//! Hiding secrets while deserializing loosely-typed data
+//!
+//! When deserializing into a typed struct, a field of type [`Hide`](crate::Hide) keeps its value
+//! hidden. When deserializing into `serde_json::Value`, or a map, there is no such field.
+//! [`AutoHide`] wraps a deserializer and replaces the string values of fields whose keys match
+//! one of a list of patterns with `***`. If required, the original values can be collected into
+//! a [`SecretMap`]:
+//!
+//! ```rust
+//! use hide::de::{AutoHide, PATTERNS};
+//! use hide::SecretMap;
+//! use serde::Deserialize;
+//! use std::cell::RefCell;
+//!
+//! let secrets = RefCell::new(SecretMap::new());
+//! let mut json = serde_json::Deserializer::from_str(
+//!     r#"{"user": "admin", "db": {"password": "secret"}}"#,
+//! );
+//! let value = serde_json::Value::deserialize(AutoHide::new(&mut json, PATTERNS).collect(&secrets))
+//!     .unwrap();
+//!
+//! assert_eq!(value.to_string(), r#"{"db":{"password":"***"},"user":"admin"}"#);
+//! assert_eq!(secrets.borrow().get("db.password").unwrap().as_str(), "secret");
+//! ```
+//!
+//! Keys are matched case-insensitively, against all keys containing a pattern. All string values
+//! nested in the value of a matching key are replaced as well. The values are labeled with their
+//! path from the root, like `db.password` or `servers.0.password`. Keys of maps must be strings.
+//!
+//! Error messages of deserializers quote the offending value, like `invalid type: string
+//! "hunter2", expected u32`. [`RedactErrors`] removes such values, keeping the rest of the
+//! message. It is used when deserializing [`Hide`](crate::Hide).
+
+use crate::{sensitive, SecretMap, SUBSTITUTE};
+use serde::de::value::StringDeserializer;
+use serde::de::{
+    DeserializeSeed, Deserializer, EnumAccess, Error, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+/// Keys containing one of these are considered sensitive.
+pub const PATTERNS: &[&str] = &["password", "secret", "token", "key", "credential"];
+
+#[derive(Clone, Copy)]
+struct Context<'a> {
+    patterns: &'a [&'a str],
+    secrets: Option<&'a RefCell<SecretMap>>,
+}
+
+impl Context<'_> {
+    fn sensitive(&self, key: &str) -> bool {
+        sensitive(self.patterns, key)
+    }
+
+    fn hide(&self, label: &str, value: &str) {
+        if let Some(secrets) = self.secrets {
+            secrets.borrow_mut().insert(label, value);
+        }
+    }
+}
+
+/// A deserializer, replacing the string values of sensitive fields, see the
+/// [module documentation](self).
+pub struct AutoHide<'a, D> {
+    inner: D,
+    context: Context<'a>,
+    /// The position of the value.
+    path: Path,
+}
+
+impl<'a, D> AutoHide<'a, D> {
+    pub fn new(inner: D, patterns: &'a [&'a str]) -> Self {
+        Self {
+            inner,
+            context: Context {
+                patterns,
+                secrets: None,
+            },
+            path: Path::default(),
+        }
+    }
+
+    /// Insert the original values into the map, labeled with their paths.
+    pub fn collect(mut self, secrets: &'a RefCell<SecretMap>) -> Self {
+        self.context.secrets = Some(secrets);
+        self
+    }
+
+    fn wrap<V>(self, visitor: V) -> (D, Visit<'a, V>) {
+        let visitor = Visit {
+            inner: visitor,
+            context: self.context,
+            path: self.path,
+        };
+        (self.inner, visitor)
+    }
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                let (inner, visitor) = self.wrap(visitor);
+                inner.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for AutoHide<'_, D> {
+    type Error = D::Error;
+
+    forward_deserialize!(
+        deserialize_any,
+        deserialize_bool,
+        deserialize_i8,
+        deserialize_i16,
+        deserialize_i32,
+        deserialize_i64,
+        deserialize_i128,
+        deserialize_u8,
+        deserialize_u16,
+        deserialize_u32,
+        deserialize_u64,
+        deserialize_u128,
+        deserialize_f32,
+        deserialize_f64,
+        deserialize_char,
+        deserialize_str,
+        deserialize_string,
+        deserialize_bytes,
+        deserialize_byte_buf,
+        deserialize_option,
+        deserialize_unit,
+        deserialize_seq,
+        deserialize_map,
+        deserialize_identifier,
+        deserialize_ignored_any,
+    );
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (inner, visitor) = self.wrap(visitor);
+        inner.deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (inner, visitor) = self.wrap(visitor);
+        inner.deserialize_newtype_struct(name, visitor)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (inner, visitor) = self.wrap(visitor);
+        inner.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (inner, visitor) = self.wrap(visitor);
+        inner.deserialize_tuple_struct(name, len, visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (inner, visitor) = self.wrap(visitor);
+        inner.deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (inner, visitor) = self.wrap(visitor);
+        inner.deserialize_enum(name, variants, visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+struct Visit<'a, V> {
+    inner: V,
+    context: Context<'a>,
+    path: Path,
+}
+
+impl<'a, V> Visit<'a, V> {
+    fn deserializer<D>(&self, inner: D) -> AutoHide<'a, D> {
+        AutoHide {
+            inner,
+            context: self.context,
+            path: self.path.clone(),
+        }
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident: $ty:ty),* $(,)?) => {
+        $(
+            fn $method<E: Error>(self, value: $ty) -> Result<Self::Value, E> {
+                self.inner.$method(value)
+            }
+        )*
+    };
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for Visit<'_, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.inner.expecting(f)
+    }
+
+    forward_visit!(
+        visit_bool: bool,
+        visit_i8: i8,
+        visit_i16: i16,
+        visit_i32: i32,
+        visit_i64: i64,
+        visit_i128: i128,
+        visit_u8: u8,
+        visit_u16: u16,
+        visit_u32: u32,
+        visit_u64: u64,
+        visit_u128: u128,
+        visit_f32: f32,
+        visit_f64: f64,
+        visit_char: char,
+        visit_bytes: &[u8],
+        visit_borrowed_bytes: &'de [u8],
+        visit_byte_buf: Vec<u8>,
+    );
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+        match self.path.label() {
+            Some(label) => {
+                self.context.hide(label, value);
+                self.inner.visit_str(SUBSTITUTE)
+            }
+            None => self.inner.visit_str(value),
+        }
+    }
+
+    fn visit_borrowed_str<E: Error>(self, value: &'de str) -> Result<Self::Value, E> {
+        match self.path.hidden {
+            true => self.visit_str(value),
+            false => self.inner.visit_borrowed_str(value),
+        }
+    }
+
+    fn visit_string<E: Error>(self, value: String) -> Result<Self::Value, E> {
+        match self.path.hidden {
+            true => self.visit_str(&value),
+            false => self.inner.visit_string(value),
+        }
+    }
+
+    fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+        self.inner.visit_none()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let deserializer = self.deserializer(deserializer);
+        self.inner.visit_some(deserializer)
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+        self.inner.visit_unit()
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        let deserializer = self.deserializer(deserializer);
+        self.inner.visit_newtype_struct(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        self.inner.visit_seq(Seq {
+            inner: seq,
+            context: self.context,
+            path: self.path,
+            index: 0,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        self.inner.visit_map(Map {
+            inner: map,
+            context: self.context,
+            path: self.path,
+            next: Path::default(),
+        })
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        self.inner.visit_enum(Enum {
+            inner: data,
+            context: self.context,
+            path: self.path,
+        })
+    }
+}
+
+/// The position of a value, from the root, used as its label if it is hidden.
+#[derive(Clone, Default)]
+struct Path {
+    /// The keys and indices leading to the value, joined by `.`.
+    keys: Option<Rc<str>>,
+    hidden: bool,
+}
+
+impl Path {
+    /// The path of a nested value, which is hidden if this one is.
+    fn join(&self, key: impl Display, hidden: bool) -> Self {
+        let keys = match &self.keys {
+            Some(parent) => format!("{parent}.{key}"),
+            None => key.to_string(),
+        };
+        Self {
+            keys: Some(keys.into()),
+            hidden: self.hidden || hidden,
+        }
+    }
+
+    /// The label, if the value is hidden.
+    fn label(&self) -> Option<&str> {
+        match self.hidden {
+            true => self.keys.as_deref(),
+            false => None,
+        }
+    }
+}
+
+/// Deserialize a value, using the context.
+struct Seed<'a, S> {
+    inner: S,
+    context: Context<'a>,
+    path: Path,
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for Seed<'_, S> {
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.inner.deserialize(AutoHide {
+            inner: deserializer,
+            context: self.context,
+            path: self.path,
+        })
+    }
+}
+
+struct Seq<'a, A> {
+    inner: A,
+    context: Context<'a>,
+    path: Path,
+    /// The index of the next element.
+    index: usize,
+}
+
+impl<'de, A: SeqAccess<'de>> SeqAccess<'de> for Seq<'_, A> {
+    type Error = A::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        let path = self.path.join(self.index, false);
+        self.index += 1;
+        self.inner.next_element_seed(Seed {
+            inner: seed,
+            context: self.context,
+            path,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+struct Map<'a, A> {
+    inner: A,
+    context: Context<'a>,
+    path: Path,
+    /// The path of the next value.
+    next: Path,
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for Map<'_, A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some(key) = self.inner.next_key::<String>()? else {
+            return Ok(None);
+        };
+
+        self.next = self.path.join(&key, self.context.sensitive(&key));
+
+        let key: StringDeserializer<A::Error> = key.into_deserializer();
+        seed.deserialize(key).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.inner.next_value_seed(Seed {
+            inner: seed,
+            context: self.context,
+            path: std::mem::take(&mut self.next),
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+/// The variant of an enum, deserializing its content using the context.
+struct Enum<'a, A> {
+    inner: A,
+    context: Context<'a>,
+    path: Path,
+}
+
+impl<'a, 'de, A: EnumAccess<'de>> EnumAccess<'de> for Enum<'a, A> {
+    type Error = A::Error;
+    type Variant = Enum<'a, A::Variant>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        // the name of the variant is not a value
+        let (value, variant) = self.inner.variant_seed(seed)?;
+        let variant = Enum {
+            inner: variant,
+            context: self.context,
+            path: self.path,
+        };
+        Ok((value, variant))
+    }
+}
+
+impl<'de, A: VariantAccess<'de>> VariantAccess<'de> for Enum<'_, A> {
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        self.inner.unit_variant()
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        self.inner.newtype_variant_seed(Seed {
+            inner: seed,
+            context: self.context,
+            path: self.path,
+        })
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.inner.tuple_variant(
+            len,
+            Visit {
+                inner: visitor,
+                context: self.context,
+                path: self.path,
+            },
+        )
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.inner.struct_variant(
+            fields,
+            Visit {
+                inner: visitor,
+                context: self.context,
+                path: self.path,
+            },
+        )
+    }
+}
+
+/// A deserializer, removing values from its error messages, see the
+/// [module documentation](self).
+///
+/// ```rust
+/// use hide::de::RedactErrors;
+/// use serde::Deserialize;
+/// use std::collections::BTreeMap;
+///
+/// let mut json = serde_json::Deserializer::from_str(r#"{"pin": "hunter2"}"#);
+/// let err = BTreeMap::<String, u32>::deserialize(RedactErrors::new(&mut json))
+///     .unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "invalid type: string, expected u32 at line 1 column 17"
+/// );
+/// ```
+///
+/// Messages which don't contain a value are passed on unchanged. Otherwise, a new error is
+/// created from the message, using [`Error::custom`].
+pub struct RedactErrors<D> {
+    inner: D,
+}
+
+impl<D> RedactErrors<D> {
+    pub fn new(inner: D) -> Self {
+        Self { inner }
+    }
+}
+
+/// Remove values from the error message.
+pub fn redact_error<E: Error>(err: E) -> E {
+    match redact_message(&err.to_string()) {
+        Cow::Borrowed(_) => err,
+        Cow::Owned(message) => E::custom(message),
+    }
+}
+
+/// Remove values, as rendered by [`Unexpected`](serde::de::Unexpected), and unknown variants.
+fn redact_message(message: &str) -> Cow<'_, str> {
+    const QUOTED: &[(&str, char)] = &[
+        ("string \"", '"'),
+        ("integer `", '`'),
+        ("floating point `", '`'),
+        ("boolean `", '`'),
+        ("character `", '`'),
+        ("unknown variant `", '`'),
+    ];
+
+    let mut result = String::new();
+    let mut rest = message;
+    'outer: while !rest.is_empty() {
+        for (prefix, end) in QUOTED {
+            let Some(value) = rest.strip_prefix(prefix) else {
+                continue;
+            };
+            if let Some(len) = quoted_len(value, *end) {
+                result.push_str(prefix.trim_end_matches([' ', *end]));
+                rest = &value[len..];
+                continue 'outer;
+            }
+        }
+
+        let next = rest.chars().next().map_or(0, char::len_utf8);
+        result.push_str(&rest[..next]);
+        rest = &rest[next..];
+    }
+
+    match result == message {
+        true => Cow::Borrowed(message),
+        false => Cow::Owned(result),
+    }
+}
+
+/// The length of a quoted value, including the closing quote.
+fn quoted_len(value: &str, end: char) -> Option<usize> {
+    let mut chars = value.char_indices();
+    // a character may be the quote itself
+    if end == '`' && value.starts_with("``") {
+        return Some(2);
+    }
+    while let Some((index, c)) = chars.next() {
+        match c {
+            // strings are rendered using `Debug`, escaping quotes
+            '\\' if end == '"' => {
+                chars.next();
+            }
+            c if c == end => return Some(index + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+macro_rules! redact_deserialize {
+    ($($method:ident $(($($arg:ident: $ty:ty),*))?),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(
+                self,
+                $($($arg: $ty,)*)?
+                visitor: V,
+            ) -> Result<V::Value, Self::Error> {
+                self.inner
+                    .$method($($($arg,)*)? visitor)
+                    .map_err(redact_error)
+            }
+        )*
+    };
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for RedactErrors<D> {
+    type Error = D::Error;
+
+    redact_deserialize!(
+        deserialize_any,
+        deserialize_bool,
+        deserialize_i8,
+        deserialize_i16,
+        deserialize_i32,
+        deserialize_i64,
+        deserialize_i128,
+        deserialize_u8,
+        deserialize_u16,
+        deserialize_u32,
+        deserialize_u64,
+        deserialize_u128,
+        deserialize_f32,
+        deserialize_f64,
+        deserialize_char,
+        deserialize_str,
+        deserialize_string,
+        deserialize_bytes,
+        deserialize_byte_buf,
+        deserialize_option,
+        deserialize_unit,
+        deserialize_unit_struct(name: &'static str),
+        deserialize_newtype_struct(name: &'static str),
+        deserialize_seq,
+        deserialize_tuple(len: usize),
+        deserialize_tuple_struct(name: &'static str, len: usize),
+        deserialize_map,
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]),
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]),
+        deserialize_identifier,
+        deserialize_ignored_any,
+    );
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::{json, Value};
+    use std::collections::BTreeMap;
+
+    fn deserialize<'de, T: Deserialize<'de>>(json: &'de str, secrets: &RefCell<SecretMap>) -> T {
+        let mut json = serde_json::Deserializer::from_str(json);
+        T::deserialize(AutoHide::new(&mut json, PATTERNS).collect(secrets)).unwrap()
+    }
+
+    #[test]
+    fn test_value() {
+        let secrets = RefCell::new(SecretMap::new());
+        let value: Value = deserialize(
+            r#"{
+                "user": "admin",
+                "API_KEY": "secret",
+                "tokens": ["a", "b", 1],
+                "credentials": {"user": "admin", "pin": "1234"},
+                "servers": [{"host": "db", "password": "secret"}],
+                "key_id": 42
+            }"#,
+            &secrets,
+        );
+
+        assert_eq!(
+            value,
+            json!({
+                "user": "admin",
+                "API_KEY": "***",
+                "tokens": ["***", "***", 1],
+                "credentials": {"user": "***", "pin": "***"},
+                "servers": [{"host": "db", "password": "***"}],
+                "key_id": 42
+            })
+        );
+
+        let secrets = secrets.into_inner();
+        assert_eq!(
+            secrets.labels().collect::<Vec<_>>(),
+            [
+                "API_KEY",
+                "credentials.pin",
+                "credentials.user",
+                "servers.0.password",
+                "tokens.0",
+                "tokens.1"
+            ]
+        );
+        assert_eq!(secrets.get("API_KEY").unwrap().as_str(), "secret");
+        assert_eq!(secrets.get("credentials.user").unwrap().as_str(), "admin");
+        assert_eq!(secrets.get("credentials.pin").unwrap().as_str(), "1234");
+        assert_eq!(secrets.get("tokens.1").unwrap().as_str(), "b");
+    }
+
+    #[test]
+    fn test_same_keys() {
+        let secrets = RefCell::new(SecretMap::new());
+        let _: Value = deserialize(
+            r#"{"db": {"password": "a"}, "cache": {"password": "b"}, "password": "c"}"#,
+            &secrets,
+        );
+
+        let secrets = secrets.into_inner();
+        assert_eq!(secrets.len(), 3);
+        assert_eq!(secrets.get("db.password").unwrap().as_str(), "a");
+        assert_eq!(secrets.get("cache.password").unwrap().as_str(), "b");
+        assert_eq!(secrets.get("password").unwrap().as_str(), "c");
+    }
+
+    #[test]
+    fn test_enum() {
+        #[derive(Deserialize)]
+        enum Credentials {
+            Plain(String),
+            Login { user: String, pin: String },
+        }
+
+        #[derive(Deserialize)]
+        struct Config {
+            credentials: Vec<Credentials>,
+        }
+
+        let secrets = RefCell::new(SecretMap::new());
+        let config: Config = deserialize(
+            r#"{"credentials": [{"Plain": "secret"}, {"Login": {"user": "admin", "pin": "1234"}}]}"#,
+            &secrets,
+        );
+
+        assert!(matches!(&config.credentials[0], Credentials::Plain(value) if value == "***"));
+        assert!(matches!(
+            &config.credentials[1],
+            Credentials::Login { user, pin } if user == "***" && pin == "***"
+        ));
+
+        let secrets = secrets.into_inner();
+        assert_eq!(secrets.get("credentials.0").unwrap().as_str(), "secret");
+        assert_eq!(secrets.get("credentials.1.pin").unwrap().as_str(), "1234");
+    }
+
+    #[test]
+    fn test_redact_message() {
+        assert_eq!(
+            redact_message(r#"invalid type: string "hun\"ter2", expected u32"#),
+            "invalid type: string, expected u32"
+        );
+        assert_eq!(
+            redact_message("invalid value: integer `1234`, expected a PIN at line 1 column 5"),
+            "invalid value: integer, expected a PIN at line 1 column 5"
+        );
+        assert_eq!(
+            redact_message("invalid type: character ```, expected a boolean"),
+            "invalid type: character, expected a boolean"
+        );
+        assert_eq!(
+            redact_message("unknown variant `secret`, expected `a` or `b`"),
+            "unknown variant, expected `a` or `b`"
+        );
+        assert!(matches!(
+            redact_message("missing field `password`"),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_hide() {
+        #[derive(Debug, Deserialize)]
+        struct Config {
+            #[allow(unused)]
+            pin: crate::Hide<u32>,
+        }
+
+        let err = serde_json::from_str::<Config>(r#"{"pin": "hunter2"}"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid type: string, expected u32 at line 1 column 17"
+        );
+        assert_eq!((err.line(), err.column()), (1, 17));
+    }
+
+    #[test]
+    fn test_typed() {
+        #[derive(Deserialize)]
+        struct Config {
+            user: String,
+            password: String,
+            extra: BTreeMap<String, String>,
+        }
+
+        let secrets = RefCell::new(SecretMap::new());
+        let config: Config = deserialize(
+            r#"{"user": "admin", "password": "secret", "extra": {"token": "secret"}}"#,
+            &secrets,
+        );
+
+        assert_eq!(config.user, "admin");
+        assert_eq!(config.password, "***");
+        assert_eq!(config.extra["token"], "***");
+        assert_eq!(secrets.borrow().len(), 2);
+    }
+}
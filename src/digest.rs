@@ -0,0 +1,68 @@
+//! Verify hidden values against a stored digest
+//!
+//! This allows storing only the digest of e.g. an API key, and verifying presented keys against
+//! it. The comparison is done in constant time.
+//!
+//! ```rust
+//! use hide::Hide;
+//!
+//! let key = Hide::new("my-api-key".to_string());
+//! assert!(key.matches_sha256("2e35b6583bdba19c898a7ca545bac207502222f6167a59924ae3953a9231c787"));
+//! ```
+
+use crate::Hide;
+use ::digest::Digest;
+
+impl<T> Hide<T>
+where
+    T: AsRef<[u8]>,
+{
+    /// Check if the digest of the value matches the expected digest.
+    pub fn matches_digest<D: Digest>(&self, expected: &[u8]) -> bool {
+        crate::ct::eq(&D::digest(self.0.as_ref()), expected)
+    }
+
+    /// Check if the digest of the value matches the expected, hex encoded, digest.
+    ///
+    /// If the expected digest isn't valid hex, this returns `false`.
+    pub fn matches_digest_hex<D: Digest>(&self, expected: &str) -> bool {
+        match crate::codec::decode_hex_str(expected.trim()) {
+            Ok(expected) => self.matches_digest::<D>(&expected),
+            Err(_) => false,
+        }
+    }
+
+    /// Check if the SHA-256 digest of the value matches the expected, hex encoded, digest.
+    #[cfg(feature = "sha2")]
+    pub fn matches_sha256(&self, expected: &str) -> bool {
+        self.matches_digest_hex::<sha2::Sha256>(expected)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use sha2::Sha256;
+
+    const DIGEST: &str = "2bb80d537b1da3e38bd30361aa855686bde0eacd7162fef6a25fe97bf527a25b";
+
+    #[test]
+    fn test_sha256() {
+        let key = Hide::new("secret".to_string());
+
+        assert!(key.matches_sha256(DIGEST));
+        assert!(key.matches_sha256(&DIGEST.to_uppercase()));
+        assert!(!key.matches_sha256(&DIGEST[2..]));
+        assert!(!key.matches_sha256("not hex"));
+        assert!(!Hide::new("Secret").matches_sha256(DIGEST));
+    }
+
+    #[test]
+    fn test_digest() {
+        let key = Hide::new(b"secret".to_vec());
+        let digest = Sha256::digest(b"secret");
+
+        assert!(key.matches_digest::<Sha256>(&digest));
+        assert!(!key.matches_digest::<Sha256>(&digest[1..]));
+    }
+}
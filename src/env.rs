@@ -0,0 +1,122 @@
+//! Working with secrets from environment variables
+
+use crate::{Hide, SecretMap};
+
+/// Compare a presented value with the value of an environment variable, in constant time.
+///
+/// If the variable is not set, is empty, or isn't valid unicode, this returns `false`. The
+/// temporary copy of the variable's value is wiped afterwards.
+///
+/// ```rust
+/// use hide::Hide;
+///
+/// # std::env::set_var("ADMIN_TOKEN", "secret");
+/// let presented = Hide::new("secret".to_string());
+/// assert!(hide::env::ct_compare("ADMIN_TOKEN", &presented));
+/// ```
+pub fn ct_compare(name: &str, presented: &Hide<String>) -> bool {
+    let Some(Ok(mut expected)) = std::env::var_os(name).map(|value| value.into_string()) else {
+        return false;
+    };
+
+    let result = !expected.is_empty() && crate::ct::eq(expected.as_bytes(), presented.0.as_bytes());
+    crate::builder::wipe(&mut expected);
+
+    result
+}
+
+/// Move secrets out of the environment, into the global store and the scrubbing registry.
+///
+/// Variables with a name containing one of the patterns (ignoring the case) are added to the
+/// [`global`](crate::global) store, labeled by their name, and registered with
+/// [`scrub::register`](crate::scrub::register). Afterwards, they are removed from the environment,
+/// so that dumps of the environment and child processes don't see them. Variables with a name or
+/// value which isn't valid unicode are left untouched.
+///
+/// Returns the names of the moved variables. Modifying the environment isn't thread-safe, so this
+/// should be called early in `main`, before spawning any threads. The environment's own copy of a
+/// value is only removed, not wiped.
+///
+/// ```rust
+/// # std::env::set_var("APP_API_TOKEN", "secret");
+/// let swept = hide::env::sweep(&["app_api_token"]);
+/// assert_eq!(swept, ["APP_API_TOKEN"]);
+///
+/// assert!(std::env::var_os("APP_API_TOKEN").is_none());
+/// assert_eq!(hide::global::get("APP_API_TOKEN").unwrap().0, "secret");
+/// ```
+pub fn sweep(patterns: &[&str]) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut secrets = SecretMap::new();
+
+    for (name, value) in std::env::vars_os() {
+        let Some(name) = name.to_str() else {
+            continue;
+        };
+        if !crate::sensitive(patterns, name) {
+            continue;
+        }
+        let Ok(value) = value.into_string() else {
+            continue;
+        };
+
+        let value = Hide(value);
+        crate::scrub::register(&value);
+        secrets.insert(name, value);
+        names.push(name.to_string());
+    }
+
+    for name in &names {
+        std::env::remove_var(name);
+    }
+    crate::global::extend(secrets);
+
+    names
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ct_compare() {
+        std::env::set_var("HIDE_TEST_CT_COMPARE", "secret");
+        std::env::set_var("HIDE_TEST_CT_COMPARE_EMPTY", "");
+
+        assert!(ct_compare(
+            "HIDE_TEST_CT_COMPARE",
+            &Hide::new("secret".into())
+        ));
+        assert!(!ct_compare(
+            "HIDE_TEST_CT_COMPARE",
+            &Hide::new("Secret".into())
+        ));
+        assert!(!ct_compare(
+            "HIDE_TEST_CT_COMPARE",
+            &Hide::new("secret2".into())
+        ));
+        assert!(!ct_compare(
+            "HIDE_TEST_CT_COMPARE_EMPTY",
+            &Hide::new("".into())
+        ));
+        assert!(!ct_compare(
+            "HIDE_TEST_CT_COMPARE_UNSET",
+            &Hide::new("".into())
+        ));
+    }
+
+    #[test]
+    fn test_sweep() {
+        std::env::set_var("HIDE_TEST_SWEEP_TOKEN", "sweep-secret");
+        std::env::set_var("HIDE_TEST_SWEEP_OTHER", "public");
+
+        let swept = sweep(&["test_sweep_token"]);
+        assert_eq!(swept, ["HIDE_TEST_SWEEP_TOKEN"]);
+
+        assert!(std::env::var_os("HIDE_TEST_SWEEP_TOKEN").is_none());
+        assert_eq!(std::env::var("HIDE_TEST_SWEEP_OTHER").unwrap(), "public");
+        assert_eq!(crate::scrub::scrub("was sweep-secret"), "was ***");
+
+        assert!(sweep(&["test_sweep_token"]).is_empty());
+    }
+}
@@ -0,0 +1,89 @@
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+/// An exposed hidden value, as returned by [`Hide::expose`](crate::Hide::expose).
+///
+/// This only dereferences to the value. It implements neither [`Debug`](std::fmt::Debug) nor
+/// [`Display`](std::fmt::Display), and isn't [`Send`], so that it is used right away, instead of
+/// being logged or sent somewhere else:
+///
+/// ```rust,compile_fail
+/// use hide::Hide;
+///
+/// let password = Hide::new("secret".to_string());
+/// println!("{:?}", password.expose("password"));
+/// ```
+///
+/// ```rust,compile_fail
+/// use hide::Hide;
+///
+/// let password = Hide::new("secret".to_string());
+/// let exposed = password.expose("password");
+/// std::thread::scope(|s| {
+///     s.spawn(move || exposed.len());
+/// });
+/// ```
+pub struct Exposed<'a, T: ?Sized> {
+    value: &'a T,
+    _marker: PhantomData<*const ()>,
+}
+
+impl<'a, T: ?Sized> Exposed<'a, T> {
+    pub(crate) fn new(value: &'a T) -> Self {
+        Self {
+            value,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized> Deref for Exposed<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.value
+    }
+}
+
+/// A mutably exposed hidden value, like [`Exposed`].
+pub struct ExposedMut<'a, T: ?Sized> {
+    value: &'a mut T,
+    _marker: PhantomData<*const ()>,
+}
+
+impl<'a, T: ?Sized> ExposedMut<'a, T> {
+    pub(crate) fn new(value: &'a mut T) -> Self {
+        Self {
+            value,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized> Deref for ExposedMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.value
+    }
+}
+
+impl<T: ?Sized> DerefMut for ExposedMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Hide;
+
+    #[test]
+    fn test_exposed() {
+        let mut password = Hide::new("secret".to_string());
+        assert_eq!(password.expose("password").len(), 6);
+
+        password.expose_mut("password").push('!');
+        assert_eq!(*password.expose("password"), "secret!");
+    }
+}
@@ -0,0 +1,92 @@
+use crate::Hide;
+
+/// Wrap any value in [`Hide`], using a method:
+///
+/// ```rust
+/// use hide::Hideable;
+///
+/// let port = 5432.hide();
+/// let password = "secret".hide_string();
+///
+/// assert_eq!(format!("{port:?} {password:?}"), "*** ***");
+/// ```
+pub trait Hideable: Sized {
+    /// Wrap the value.
+    fn hide(self) -> Hide<Self> {
+        Hide(self)
+    }
+
+    /// Wrap an owned copy of a string.
+    fn hide_string(&self) -> Hide<String>
+    where
+        Self: AsRef<str>,
+    {
+        Hide(self.as_ref().to_owned())
+    }
+}
+
+impl<T> Hideable for T {}
+
+/// Wrap the value of an [`Option`] in [`Hide`], e.g. in a chain of combinators:
+///
+/// ```rust
+/// use hide::HideOptionExt;
+/// use std::collections::HashMap;
+///
+/// let secrets = HashMap::from([("token", "secret")]);
+/// let token = secrets.get("token").copied().hide_inner();
+///
+/// assert_eq!(format!("{token:?}"), "Some(***)");
+/// ```
+pub trait HideOptionExt<T> {
+    fn hide_inner(self) -> Option<Hide<T>>;
+}
+
+impl<T> HideOptionExt<T> for Option<T> {
+    fn hide_inner(self) -> Option<Hide<T>> {
+        self.map(Hide)
+    }
+}
+
+/// Wrap the success value of a [`Result`] in [`Hide`], e.g. in a chain of combinators:
+///
+/// ```rust
+/// use hide::HideResultExt;
+///
+/// let token = std::env::var("HIDE_EXAMPLE_TOKEN").hide_ok();
+/// assert!(token.is_err());
+/// ```
+pub trait HideResultExt<T, E> {
+    fn hide_ok(self) -> Result<Hide<T>, E>;
+}
+
+impl<T, E> HideResultExt<T, E> for Result<T, E> {
+    fn hide_ok(self) -> Result<Hide<T>, E> {
+        self.map(Hide)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hide() {
+        let token: Hide<Vec<u8>> = vec![1, 2, 3].hide();
+        assert_eq!(token.0, [1, 2, 3]);
+
+        let password = String::from("secret");
+        let hidden: Hide<String> = password.hide_string();
+        assert_eq!(hidden.0, password);
+        assert_eq!(password.hide().0, "secret");
+    }
+
+    #[test]
+    fn test_option_result() {
+        assert_eq!(Some(1).hide_inner(), Some(Hide(1)));
+        assert_eq!(None::<u8>.hide_inner(), None);
+
+        assert_eq!(Ok::<_, ()>("secret").hide_ok(), Ok(Hide("secret")));
+        assert_eq!(Err::<&str, _>(1).hide_ok(), Err(1));
+    }
+}
@@ -0,0 +1,129 @@
+//! Support for carrying hidden values across FFI boundaries
+//!
+//! Secrets passed to C libraries, like passwords for PAM or libpq, can stay hidden until they are
+//! passed across the boundary, using a [`Hide<CString>`](crate::Hide):
+//!
+//! ```rust
+//! use hide::Hide;
+//! use std::ffi::CStr;
+//!
+//! let password = Hide::new("secret".to_string());
+//! let password = password.to_cstring()?;
+//!
+//! assert_eq!(format!("{password:?}"), "***");
+//! let ptr = AsRef::<CStr>::as_ref(&password).as_ptr();
+//! # assert!(!ptr.is_null());
+//! # Ok::<(), hide::ffi::HiddenNulError>(())
+//! ```
+
+use crate::Hide;
+use std::ffi::{CStr, CString};
+use std::fmt::{Display, Formatter};
+
+impl<T: AsRef<str>> Hide<T> {
+    /// Copy the value into a C string.
+    ///
+    /// The copy is created with the capacity for the terminating nul byte, so that no unprotected
+    /// copy is left behind. Fails if the value contains a nul byte, in which case the copy is
+    /// wiped.
+    pub fn to_cstring(&self) -> Result<Hide<CString>, HiddenNulError> {
+        let value = self.0.as_ref().as_bytes();
+        let mut bytes = Vec::with_capacity(value.len() + 1);
+        bytes.extend_from_slice(value);
+
+        CString::new(bytes).map(Hide).map_err(|err| {
+            let position = err.nul_position();
+            crate::builder::wipe_bytes(&mut err.into_vec());
+            HiddenNulError { position }
+        })
+    }
+}
+
+impl AsRef<CStr> for Hide<CString> {
+    fn as_ref(&self) -> &CStr {
+        &self.0
+    }
+}
+
+impl AsRef<CStr> for Hide<&CStr> {
+    fn as_ref(&self) -> &CStr {
+        self.0
+    }
+}
+
+/// A hidden value contains a nul byte, and can't be converted into a C string.
+///
+/// Unlike [`NulError`](std::ffi::NulError), this doesn't contain the value.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HiddenNulError {
+    position: usize,
+}
+
+impl HiddenNulError {
+    /// The position of the nul byte.
+    pub fn nul_position(&self) -> usize {
+        self.position
+    }
+}
+
+impl Display for HiddenNulError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "nul byte found in hidden value at position {}",
+            self.position
+        )
+    }
+}
+
+impl std::error::Error for HiddenNulError {}
+
+/// A hidden string, which can be used in UniFFI interfaces.
+///
+/// It is lowered to a plain `String` in the generated bindings, and lifted back into a
+/// [`Hide`](crate::Hide) on the Rust side. Crates using it in their interface must declare it
+/// using:
+///
+/// ```rust,ignore
+/// uniffi::use_remote_type!(hide::ffi::HiddenString);
+/// ```
+#[cfg(feature = "uniffi")]
+pub type HiddenString = Hide<String>;
+
+#[cfg(feature = "uniffi")]
+uniffi::custom_type!(HiddenString, String, {
+    lower: |value| value.0,
+    try_lift: |value| Ok(Hide(value)),
+});
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(feature = "uniffi")]
+    use crate::UniFfiTag;
+    #[cfg(feature = "uniffi")]
+    use uniffi::{Lift, Lower};
+
+    #[test]
+    fn test_to_cstring() {
+        let value = Hide("secret").to_cstring().unwrap();
+        assert_eq!(value.0.as_bytes_with_nul(), b"secret\0");
+        assert_eq!(AsRef::<CStr>::as_ref(&value).to_bytes(), b"secret");
+        assert_eq!(format!("{value:?}"), "***");
+
+        let err = Hide("sec\0ret".to_string()).to_cstring().unwrap_err();
+        assert_eq!(err.nul_position(), 3);
+        assert_eq!(
+            err.to_string(),
+            "nul byte found in hidden value at position 3"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "uniffi")]
+    fn test_roundtrip() {
+        let lowered = <HiddenString as Lower<UniFfiTag>>::lower(Hide("foo".to_string()));
+        let lifted = <HiddenString as Lift<UniFfiTag>>::try_lift(lowered).unwrap();
+        assert_eq!(lifted.0, "foo");
+    }
+}
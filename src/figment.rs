@@ -0,0 +1,317 @@
+//! Integration with `figment`
+//!
+//! [`Hide`](crate::Hide) can be extracted from a [`Figment`] like any other value. However, the
+//! [`Debug`] output of a [`Figment`], as well as its errors, may contain the values of secret keys.
+//! [`SecretKeys`] marks keys as secret, and redacts them from that output:
+//!
+//! ```rust
+//! use figment::{Figment, providers::{Format, Toml}};
+//! use hide::{Hide, figment::SecretKeys};
+//!
+//! #[derive(serde::Deserialize)]
+//! struct Config {
+//!     database: Database,
+//! }
+//!
+//! #[derive(serde::Deserialize)]
+//! struct Database {
+//!     user: String,
+//!     password: Hide<u32>,
+//! }
+//!
+//! let secrets = SecretKeys::new(["database.password"]);
+//! let figment = Figment::from(secrets.provider(Toml::string(r#"
+//!     [database]
+//!     user = "admin"
+//!     password = "hunter2"
+//! "#)));
+//!
+//! let err = figment
+//!     .extract::<Config>()
+//!     .map_err(|err| secrets.redact_error(err))
+//!     .err()
+//!     .unwrap();
+//!
+//! assert!(!err.to_string().contains("hunter2"));
+//! assert!(!format!("{:?}", secrets.redact(&figment)).contains("hunter2"));
+//! ```
+
+use crate::SUBSTITUTE;
+use ::figment::error::{Actual, Kind};
+use ::figment::value::{Dict, Map, Value};
+use ::figment::{Error, Figment, Metadata, Profile, Provider};
+use std::fmt::{Debug, Formatter};
+
+/// A set of keys, which are considered secret.
+///
+/// Keys use the dotted notation of figment (e.g. `database.password`), and also cover all nested
+/// keys.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SecretKeys {
+    keys: Vec<Vec<String>>,
+}
+
+impl SecretKeys {
+    pub fn new<I>(keys: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        Self::default().keys(keys)
+    }
+
+    /// Add a key.
+    pub fn key(mut self, key: impl AsRef<str>) -> Self {
+        self.keys
+            .push(key.as_ref().split('.').map(ToString::to_string).collect());
+        self
+    }
+
+    /// Add multiple keys.
+    pub fn keys<I>(self, keys: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        keys.into_iter().fold(self, |secrets, key| secrets.key(key))
+    }
+
+    /// Check if the path of a value is secret.
+    pub fn is_secret<S: AsRef<str>>(&self, path: &[S]) -> bool {
+        self.keys.iter().any(|key| {
+            key.len() <= path.len() && key.iter().zip(path).all(|(k, p)| k == p.as_ref())
+        })
+    }
+
+    /// Wrap a provider, redacting secret keys from its errors.
+    pub fn provider<P: Provider>(&self, provider: P) -> Secrets<P> {
+        Secrets {
+            provider,
+            keys: self.clone(),
+        }
+    }
+
+    /// Redact the values of secret keys from an error, and all errors chained to it.
+    pub fn redact_error(&self, error: Error) -> Error {
+        error
+            .into_iter()
+            .map(|mut error| {
+                if self.is_secret(&error.path) {
+                    error.kind = redact_kind(error.kind);
+                }
+                error
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            // iterating yields the most recent error first, chaining needs the oldest first
+            .rev()
+            .reduce(Error::chain)
+            .unwrap_or_else(|| Kind::Message(SUBSTITUTE.to_string()).into())
+    }
+
+    /// Get a view of the figment, which redacts secret keys from the [`Debug`] output.
+    pub fn redact<'a>(&'a self, figment: &'a Figment) -> Redacted<'a> {
+        Redacted {
+            keys: self,
+            figment,
+        }
+    }
+
+    fn redact_dict(&self, dict: &mut Dict, path: &mut Vec<String>) {
+        for (key, value) in dict.iter_mut() {
+            path.push(key.clone());
+            self.redact_value(value, path);
+            path.pop();
+        }
+    }
+
+    fn redact_value(&self, value: &mut Value, path: &mut Vec<String>) {
+        if self.is_secret(path) {
+            *value = Value::String(value.tag(), SUBSTITUTE.to_string());
+            return;
+        }
+
+        match value {
+            Value::Dict(_, dict) => self.redact_dict(dict, path),
+            Value::Array(_, values) => {
+                for (n, value) in values.iter_mut().enumerate() {
+                    path.push(n.to_string());
+                    self.redact_value(value, path);
+                    path.pop();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn redact_kind(kind: Kind) -> Kind {
+    let actual = || Actual::Other(SUBSTITUTE.to_string());
+    match kind {
+        Kind::Message(_) => Kind::Message(format!("invalid value: {SUBSTITUTE}")),
+        Kind::InvalidType(_, expected) => Kind::InvalidType(actual(), expected),
+        Kind::InvalidValue(_, expected) => Kind::InvalidValue(actual(), expected),
+        Kind::UnknownVariant(_, expected) => Kind::UnknownVariant(SUBSTITUTE.to_string(), expected),
+        Kind::Unsupported(_) => Kind::Unsupported(actual()),
+        Kind::UnsupportedKey(_, expected) => Kind::UnsupportedKey(actual(), expected),
+        kind => kind,
+    }
+}
+
+/// A provider, marking keys as secret.
+///
+/// The data is passed through unchanged, so that it can still be extracted. Only errors of the
+/// provider are redacted.
+#[derive(Clone)]
+pub struct Secrets<P> {
+    provider: P,
+    keys: SecretKeys,
+}
+
+impl<P> Secrets<P> {
+    pub fn keys(&self) -> &SecretKeys {
+        &self.keys
+    }
+}
+
+impl<P: Provider> Provider for Secrets<P> {
+    fn metadata(&self) -> Metadata {
+        self.provider.metadata()
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        self.provider
+            .data()
+            .map_err(|err| self.keys.redact_error(err))
+    }
+
+    fn profile(&self) -> Option<Profile> {
+        self.provider.profile()
+    }
+}
+
+impl<P> Debug for Secrets<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Secrets")
+            .field("keys", &self.keys)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A view of a [`Figment`], redacting secret keys from the [`Debug`] output.
+///
+/// Created by [`SecretKeys::redact`].
+pub struct Redacted<'a> {
+    keys: &'a SecretKeys,
+    figment: &'a Figment,
+}
+
+impl Debug for Redacted<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut s = f.debug_struct("Figment");
+        s.field("profile", self.figment.profile());
+        match self.figment.data() {
+            Ok(mut data) => {
+                for dict in data.values_mut() {
+                    self.keys.redact_dict(dict, &mut vec![]);
+                }
+                s.field("value", &data);
+            }
+            Err(err) => {
+                s.field("error", &self.keys.redact_error(err));
+            }
+        }
+        s.finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Hide;
+    use ::figment::providers::{Format, Serialized, Toml};
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Config {
+        user: String,
+        password: Hide<String>,
+    }
+
+    #[test]
+    fn test_extract() {
+        let figment = Figment::from(Toml::string(
+            r#"
+user = "admin"
+password = "hunter2"
+"#,
+        ));
+
+        let config: Config = figment.extract().unwrap();
+        assert_eq!(config.user, "admin");
+        assert_eq!(config.password.0, "hunter2");
+    }
+
+    #[test]
+    fn test_is_secret() {
+        let keys = SecretKeys::new(["database.password", "token"]);
+
+        assert!(keys.is_secret(&["token"]));
+        assert!(keys.is_secret(&["token", "value"]));
+        assert!(keys.is_secret(&["database", "password"]));
+        assert!(!keys.is_secret(&["database"]));
+        assert!(!keys.is_secret(&["database", "user"]));
+    }
+
+    #[test]
+    fn test_redact_debug() {
+        let keys = SecretKeys::new(["password", "tokens"]);
+        let figment = Figment::from(keys.provider(Serialized::defaults(serde_json::json!({
+            "user": "admin",
+            "password": "hunter2",
+            "tokens": ["foo", "bar"],
+        }))));
+
+        let debug = format!("{:?}", keys.redact(&figment));
+        assert!(debug.contains("admin"));
+        assert!(!debug.contains("hunter2"));
+        assert!(!debug.contains("foo"));
+        assert!(!debug.contains("bar"));
+
+        // the data is still available
+        let config: Config = figment.extract().unwrap();
+        assert_eq!(config.password.0, "hunter2");
+    }
+
+    #[test]
+    fn test_redact_error() {
+        let keys = SecretKeys::new(["password"]);
+        let figment = Figment::from(Serialized::defaults(serde_json::json!({
+            "user": "admin",
+            "password": true,
+        })));
+
+        let err = figment.extract::<Config>().unwrap_err();
+        assert!(err.to_string().contains("bool true"));
+        let err = keys.redact_error(err);
+        assert!(!err.to_string().contains("true"));
+        assert_eq!(err.path, ["password"]);
+    }
+
+    #[test]
+    fn test_redact_error_chain() {
+        let keys = SecretKeys::new(["password"]);
+        let err = Error::from("user: admin")
+            .with_path("user")
+            .chain(Error::from("password: hunter2").with_path("password"));
+
+        let errors = keys
+            .redact_error(err)
+            .into_iter()
+            .map(|err| err.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].starts_with("invalid value: ***"));
+        assert!(errors[1].starts_with("user: admin"));
+    }
+}
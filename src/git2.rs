@@ -0,0 +1,157 @@
+//! Integration with `git2`
+
+use crate::{Credentials, Hide};
+use ::git2::{Cred, CredentialType, Error, RemoteCallbacks};
+use std::path::PathBuf;
+
+/// Credentials for accessing remote repositories, providing the credentials callback of `git2`.
+///
+/// Tokens and passphrases stay hidden until `libgit2` asks for them. Each kind of credentials is
+/// only offered once per operation, so that rejected credentials fail the operation, instead of
+/// being retried forever.
+///
+/// ```rust
+/// use git2::{build::RepoBuilder, FetchOptions};
+/// use hide::{GitCredentials, Hide};
+///
+/// let token = Hide::new("ghp_secret".to_string());
+///
+/// let mut options = FetchOptions::new();
+/// options.remote_callbacks(GitCredentials::new().token(token).callbacks());
+///
+/// let mut builder = RepoBuilder::new();
+/// builder.fetch_options(options);
+/// // builder.clone("https://github.com/example/private.git", path)?;
+/// ```
+#[derive(Debug, Default)]
+#[cfg_attr(not(feature = "paranoid"), derive(Clone))]
+pub struct GitCredentials {
+    userpass: Option<Credentials>,
+    ssh_key: Option<SshKey>,
+}
+
+#[derive(Debug)]
+#[cfg_attr(not(feature = "paranoid"), derive(Clone))]
+struct SshKey {
+    private_key: PathBuf,
+    passphrase: Option<Hide<String>>,
+}
+
+/// The username for tokens, which is accepted by GitHub, and ignored by most other hosts.
+const TOKEN_USERNAME: &str = "x-access-token";
+
+/// The username for SSH, if the URL doesn't contain one.
+const SSH_USERNAME: &str = "git";
+
+impl GitCredentials {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Authenticate using a token over HTTPS, like a GitHub access token.
+    pub fn token(self, token: impl Into<Hide<String>>) -> Self {
+        self.userpass(Credentials::new(TOKEN_USERNAME, token))
+    }
+
+    /// Authenticate using a username and password over HTTPS.
+    pub fn userpass(mut self, credentials: Credentials) -> Self {
+        self.userpass = Some(credentials);
+        self
+    }
+
+    /// Authenticate using the private key in the file over SSH.
+    ///
+    /// The username is taken from the URL, or `git`, if it doesn't contain one.
+    pub fn ssh_key(
+        mut self,
+        private_key: impl Into<PathBuf>,
+        passphrase: Option<Hide<String>>,
+    ) -> Self {
+        self.ssh_key = Some(SshKey {
+            private_key: private_key.into(),
+            passphrase,
+        });
+        self
+    }
+
+    /// Create the callbacks for a remote operation, like fetching or pushing.
+    pub fn callbacks<'a>(self) -> RemoteCallbacks<'a> {
+        let mut callbacks = RemoteCallbacks::new();
+        let mut tried = CredentialType::empty();
+        callbacks.credentials(move |_url, username, allowed| {
+            self.credential(username, allowed, &mut tried)
+        });
+        callbacks
+    }
+
+    /// Get the next credentials, which were not tried yet.
+    fn credential(
+        &self,
+        username: Option<&str>,
+        allowed: CredentialType,
+        tried: &mut CredentialType,
+    ) -> Result<Cred, Error> {
+        let mut next = |kind: CredentialType| {
+            let next = allowed.contains(kind) && !tried.contains(kind);
+            if next {
+                tried.insert(kind);
+            }
+            next
+        };
+
+        if let Some(credentials) = self.userpass.as_ref() {
+            if next(CredentialType::USER_PASS_PLAINTEXT) {
+                return Cred::userpass_plaintext(&credentials.username, &credentials.password.0);
+            }
+        }
+
+        if let Some(key) = self.ssh_key.as_ref() {
+            let username = username.unwrap_or(SSH_USERNAME);
+            if next(CredentialType::USERNAME) {
+                return Cred::username(username);
+            }
+            if next(CredentialType::SSH_KEY) {
+                let passphrase = key
+                    .passphrase
+                    .as_ref()
+                    .map(|passphrase| passphrase.0.as_str());
+                return Cred::ssh_key(username, None, &key.private_key, passphrase);
+            }
+        }
+
+        Err(Error::from_str("no credentials accepted by the remote"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_credential() {
+        let credentials = GitCredentials::new().token("secret");
+        let mut tried = CredentialType::empty();
+
+        let allowed = CredentialType::USER_PASS_PLAINTEXT;
+        let cred = credentials.credential(None, allowed, &mut tried).unwrap();
+        assert!(cred.has_username());
+        assert!(credentials.credential(None, allowed, &mut tried).is_err());
+
+        let allowed = CredentialType::SSH_KEY;
+        let mut tried = CredentialType::empty();
+        assert!(credentials.credential(None, allowed, &mut tried).is_err());
+    }
+
+    #[test]
+    fn test_ssh_username() {
+        let credentials = GitCredentials::new().ssh_key("/nonexistent/id_ed25519", None);
+        let mut tried = CredentialType::empty();
+
+        let allowed = CredentialType::USERNAME;
+        let cred = credentials
+            .credential(Some("deploy"), allowed, &mut tried)
+            .unwrap();
+        assert!(cred.has_username());
+        assert!(credentials.credential(None, allowed, &mut tried).is_err());
+    }
+}
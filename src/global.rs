@@ -0,0 +1,89 @@
+//! A process-wide store of hidden values
+//!
+//! This allows code deep down the call stack to access credentials, without passing them through
+//! every layer:
+//!
+//! ```rust
+//! use hide::{global, SecretMap};
+//!
+//! let mut secrets = SecretMap::new();
+//! secrets.insert("api_token", "secret");
+//! global::init(secrets);
+//!
+//! let token = global::get("api_token").unwrap();
+//! assert_eq!(token.as_str(), "secret");
+//! ```
+//!
+//! Calling [`init`] again replaces all values, e.g. after credentials were rotated.
+
+use crate::{Hide, SecretMap};
+use std::sync::{PoisonError, RwLock};
+
+static STORE: RwLock<Option<SecretMap>> = RwLock::new(None);
+
+/// Initialize the store, replacing all previous values.
+pub fn init(secrets: SecretMap) {
+    *STORE.write().unwrap_or_else(PoisonError::into_inner) = Some(secrets);
+}
+
+/// Add values to the store, initializing it if needed, and replacing values with the same labels.
+pub fn extend(secrets: SecretMap) {
+    STORE
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get_or_insert_with(SecretMap::new)
+        .extend(secrets);
+}
+
+/// Check if the store was initialized.
+pub fn is_initialized() -> bool {
+    STORE
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .is_some()
+}
+
+/// Get a copy of a value.
+///
+/// Returns `None` if the store wasn't initialized, or doesn't have a value for the label.
+pub fn get(label: &str) -> Option<Hide<String>> {
+    with(label, |value| Hide(value.0.clone()))
+}
+
+/// Run the closure with a value, without copying it.
+pub fn with<F, R>(label: &str, f: F) -> Option<R>
+where
+    F: FnOnce(&Hide<String>) -> R,
+{
+    STORE
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()?
+        .get(label)
+        .map(f)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_store() {
+        init([("token", "foo")].into_iter().collect());
+        assert!(is_initialized());
+        assert_eq!(get("token").unwrap().as_str(), "foo");
+        assert_eq!(with("token", |token| token.len()), Some(3));
+        assert!(get("password").is_none());
+
+        init([("token", "bar")].into_iter().collect());
+        assert_eq!(get("token").unwrap().as_str(), "bar");
+
+        extend(
+            [("token", "baz"), ("password", "qux")]
+                .into_iter()
+                .collect(),
+        );
+        assert_eq!(get("token").unwrap().as_str(), "baz");
+        assert_eq!(get("password").unwrap().as_str(), "qux");
+    }
+}
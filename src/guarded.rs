@@ -0,0 +1,123 @@
+use std::fmt::{Debug, Formatter};
+use std::io;
+use std::mem::size_of;
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+
+/// A hidden value, stored in its own locked pages, surrounded by inaccessible guard pages.
+///
+/// The allocation works like `sodium_malloc` of libsodium: the value is placed at the end of its
+/// pages, directly in front of a guard page, so that overflows and overreads crash the process,
+/// instead of leaking the value. A canary in front of the value is checked when it is dropped,
+/// aborting the process if it was overwritten. The pages are locked, so that they aren't swapped
+/// to disk, and are wiped when dropped.
+///
+/// ```rust
+/// use hide::GuardedHide;
+///
+/// let mut key = GuardedHide::<[u8; 32]>::with_default(|key| key[0] = 42).unwrap();
+/// key[1] = 23;
+///
+/// assert_eq!(key[..2], [42, 23]);
+/// assert_eq!(format!("{key:?}"), "***");
+/// ```
+///
+/// Only the value itself is protected, not memory it owns on the heap, like the buffer of a
+/// `String`. So this is intended for fixed sized values, like long-lived keys. Each value uses at
+/// least four pages of memory.
+pub struct GuardedHide<T> {
+    value: NonNull<T>,
+}
+
+// the value is owned, like in a `Box`
+unsafe impl<T: Send> Send for GuardedHide<T> {}
+unsafe impl<T: Sync> Sync for GuardedHide<T> {}
+
+impl<T> GuardedHide<T> {
+    /// Move the value into a guarded allocation.
+    ///
+    /// Moving may leave copies of the value behind, e.g. on the stack. Use [`Self::with_default`]
+    /// to initialize the value in place instead.
+    pub fn new(value: T) -> io::Result<Self> {
+        let guarded = Self::allocate()?;
+        // SAFETY: the allocation is valid for writes of `T`, and properly aligned
+        unsafe { guarded.value.as_ptr().write(value) };
+        Ok(guarded)
+    }
+
+    /// Create a guarded default value, and initialize it in place.
+    pub fn with_default(init: impl FnOnce(&mut T)) -> io::Result<Self>
+    where
+        T: Default,
+    {
+        let mut guarded = Self::new(T::default())?;
+        init(&mut guarded);
+        Ok(guarded)
+    }
+
+    fn allocate() -> io::Result<Self> {
+        // SAFETY: the uninitialized memory is only written to, before being read
+        let value = unsafe { memsec::malloc::<T>() }.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::OutOfMemory,
+                "failed to allocate guarded memory",
+            )
+        })?;
+        Ok(Self { value })
+    }
+}
+
+impl<T> Drop for GuardedHide<T> {
+    fn drop(&mut self) {
+        // SAFETY: the value was initialized and allocated by `memsec::malloc`, and isn't used
+        // afterwards
+        unsafe {
+            self.value.as_ptr().drop_in_place();
+            memsec::memzero(self.value.as_ptr().cast(), size_of::<T>());
+            memsec::free(self.value);
+        }
+    }
+}
+
+impl<T> Deref for GuardedHide<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: the value is initialized, and borrowed along with `self`
+        unsafe { self.value.as_ref() }
+    }
+}
+
+impl<T> DerefMut for GuardedHide<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: the value is initialized, and borrowed along with `self`
+        unsafe { self.value.as_mut() }
+    }
+}
+
+impl<T> Debug for GuardedHide<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        crate::write_substitute(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_guarded() {
+        let mut key = GuardedHide::new([1u8; 32]).unwrap();
+        key[31] = 2;
+        assert_eq!(key.iter().map(|b| *b as u32).sum::<u32>(), 33);
+
+        let mut password = GuardedHide::new("secret".to_string()).unwrap();
+        password.push('!');
+        assert_eq!(password.as_str(), "secret!");
+        assert_eq!(format!("{password:?}"), "***");
+
+        let value = GuardedHide::<u64>::with_default(|value| *value = 42).unwrap();
+        assert_eq!(*value, 42);
+        assert_eq!((&*value as *const u64).align_offset(8), 0);
+    }
+}
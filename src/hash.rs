@@ -0,0 +1,157 @@
+#[cfg(feature = "sealed")]
+use crate::sealed::Sealed;
+use crate::{Hide, HideFileName, HideIn, HideOnce, HideStrict, RedactDeep, Summarized};
+#[cfg(feature = "keyed-ord")]
+use std::cmp::Ordering;
+use std::collections::hash_map::RandomState;
+#[cfg(feature = "keyed-hash")]
+use std::hash::Hasher;
+use std::hash::{BuildHasher, Hash};
+use std::sync::OnceLock;
+
+/// The keys for hashing values, random for each process.
+static KEYS: OnceLock<RandomState> = OnceLock::new();
+
+fn digest<T: Hash + ?Sized>(value: &T) -> u64 {
+    KEYS.get_or_init(RandomState::new).hash_one(value)
+}
+
+/// Feed a keyed hash of the value into the hasher, instead of the value itself.
+#[cfg(feature = "keyed-hash")]
+fn keyed<T: Hash + ?Sized, H: Hasher>(value: &T, state: &mut H) {
+    state.write_u64(digest(value));
+}
+
+/// Order values by their keyed hash.
+///
+/// Only if the hashes collide, the values themselves are compared, to stay consistent with
+/// [`Eq`]. The order is stable within a process, but not across processes.
+#[cfg(feature = "keyed-ord")]
+fn keyed_cmp<T: Hash + Ord + ?Sized>(a: &T, b: &T) -> Ordering {
+    digest(a).cmp(&digest(b)).then_with(|| a.cmp(b))
+}
+
+#[cfg(feature = "keyed-hash")]
+macro_rules! keyed_hash {
+    ($ty:ident) => {
+        impl<T: Hash> Hash for $ty<T> {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                keyed(&self.0, state);
+            }
+        }
+    };
+}
+
+#[cfg(feature = "keyed-hash")]
+keyed_hash!(Hide);
+#[cfg(feature = "keyed-hash")]
+keyed_hash!(HideFileName);
+#[cfg(feature = "keyed-hash")]
+keyed_hash!(HideIn);
+#[cfg(feature = "keyed-hash")]
+keyed_hash!(HideOnce);
+#[cfg(feature = "keyed-hash")]
+keyed_hash!(HideStrict);
+#[cfg(feature = "keyed-hash")]
+keyed_hash!(RedactDeep);
+#[cfg(all(feature = "keyed-hash", feature = "sealed"))]
+keyed_hash!(Sealed);
+#[cfg(feature = "keyed-hash")]
+keyed_hash!(Summarized);
+
+#[cfg(feature = "keyed-ord")]
+macro_rules! keyed_ord {
+    ($ty:ident) => {
+        impl<T: Hash + Ord> Ord for $ty<T> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                keyed_cmp(&self.0, &other.0)
+            }
+        }
+
+        impl<T: Hash + Ord> PartialOrd for $ty<T> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+    };
+}
+
+#[cfg(feature = "keyed-ord")]
+keyed_ord!(Hide);
+#[cfg(feature = "keyed-ord")]
+keyed_ord!(HideFileName);
+#[cfg(feature = "keyed-ord")]
+keyed_ord!(HideIn);
+#[cfg(feature = "keyed-ord")]
+keyed_ord!(HideOnce);
+#[cfg(feature = "keyed-ord")]
+keyed_ord!(HideStrict);
+#[cfg(feature = "keyed-ord")]
+keyed_ord!(RedactDeep);
+#[cfg(all(feature = "keyed-ord", feature = "sealed"))]
+keyed_ord!(Sealed);
+#[cfg(feature = "keyed-ord")]
+keyed_ord!(Summarized);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Records everything written to it.
+    #[cfg(feature = "keyed-hash")]
+    #[derive(Default)]
+    struct Recorder(Vec<u8>);
+
+    #[cfg(feature = "keyed-hash")]
+    impl Hasher for Recorder {
+        fn finish(&self) -> u64 {
+            0
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            self.0.extend_from_slice(bytes);
+        }
+    }
+
+    #[cfg(feature = "keyed-hash")]
+    fn record<T: Hash>(value: &T) -> Vec<u8> {
+        let mut recorder = Recorder::default();
+        value.hash(&mut recorder);
+        recorder.0
+    }
+
+    #[cfg(feature = "keyed-hash")]
+    #[test]
+    fn test_keyed() {
+        let recorded = record(&Hide("secret"));
+        assert!(!recorded.windows(6).any(|window| window == b"secret"));
+        assert_eq!(recorded, record(&Hide("secret")));
+        assert_ne!(recorded, record(&Hide("other")));
+        assert_eq!(record(&HideStrict("secret")), recorded);
+        assert_eq!(record(&HideIn("secret")), recorded);
+        assert_eq!(record(&HideOnce("secret")), recorded);
+        assert_eq!(record(&RedactDeep("secret")), recorded);
+        assert_eq!(record(&Summarized("secret")), recorded);
+        // wrappers of `Hide` use its keyed hash
+        assert_eq!(
+            record(&crate::SymmetricKey::from([1u8, 2])),
+            record(&Hide(vec![1u8, 2]))
+        );
+    }
+
+    #[cfg(feature = "keyed-ord")]
+    #[test]
+    fn test_keyed_ord() {
+        let a = Hide("a");
+        let b = Hide("b");
+        assert_eq!(a.cmp(&a.clone()), Ordering::Equal);
+        assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+        assert_eq!(a.cmp(&b), digest("a").cmp(&digest("b")));
+        assert_eq!(HideStrict("a").cmp(&HideStrict("b")), a.cmp(&b));
+        assert_eq!(HideIn("a").cmp(&HideIn("b")), a.cmp(&b));
+        assert_eq!(HideOnce("a").cmp(&HideOnce("b")), a.cmp(&b));
+        assert_eq!(RedactDeep("a").cmp(&RedactDeep("b")), a.cmp(&b));
+        assert_eq!(Summarized("a").cmp(&Summarized("b")), a.cmp(&b));
+        assert_eq!(HideFileName("a").cmp(&HideFileName("b")), a.cmp(&b));
+    }
+}
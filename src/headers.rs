@@ -0,0 +1,91 @@
+//! Integration with `headers`
+//!
+//! The typed headers are not hidden when formatted, so they should only be created right before
+//! sending a request.
+
+use crate::{Credentials, Hide};
+use ::headers::authorization::{Basic, Bearer, InvalidBearerToken};
+use ::headers::Authorization;
+
+impl<T: AsRef<str>> Hide<T> {
+    /// Create an `Authorization` header, using the value as bearer token.
+    ///
+    /// ```rust
+    /// use hide::Hide;
+    ///
+    /// let token = Hide::new("secret");
+    /// let header = token.bearer_authorization().unwrap();
+    /// assert_eq!(header.token(), "secret");
+    /// ```
+    pub fn bearer_authorization(&self) -> Result<Authorization<Bearer>, InvalidBearerToken> {
+        Authorization::bearer(self.0.as_ref())
+    }
+}
+
+impl TryFrom<&Hide<String>> for Authorization<Bearer> {
+    type Error = InvalidBearerToken;
+
+    fn try_from(value: &Hide<String>) -> Result<Self, Self::Error> {
+        value.bearer_authorization()
+    }
+}
+
+impl From<Authorization<Bearer>> for Hide<String> {
+    fn from(value: Authorization<Bearer>) -> Self {
+        Hide(value.token().to_string())
+    }
+}
+
+impl Credentials {
+    /// Create an `Authorization` header, using basic authentication.
+    pub fn basic_authorization(&self) -> Authorization<Basic> {
+        Authorization::basic(&self.username, &self.password.0)
+    }
+}
+
+impl From<&Credentials> for Authorization<Basic> {
+    fn from(value: &Credentials) -> Self {
+        value.basic_authorization()
+    }
+}
+
+impl From<Authorization<Basic>> for Credentials {
+    fn from(value: Authorization<Basic>) -> Self {
+        Credentials::new(value.username(), Hide(value.password().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::headers::{Header, HeaderMap, HeaderMapExt};
+
+    #[test]
+    fn test_bearer() {
+        let token = Hide("secret".to_string());
+
+        let mut headers = HeaderMap::new();
+        headers.typed_insert(Authorization::try_from(&token).unwrap());
+        assert_eq!(headers[Authorization::<Bearer>::name()], "Bearer secret");
+
+        let header = headers.typed_get::<Authorization<Bearer>>().unwrap();
+        assert_eq!(Hide::from(header), token);
+
+        assert!(Hide("new\nline").bearer_authorization().is_err());
+    }
+
+    #[test]
+    fn test_basic() {
+        let credentials = Credentials::new("user", "secret");
+
+        let mut headers = HeaderMap::new();
+        headers.typed_insert(Authorization::from(&credentials));
+        assert_eq!(
+            headers[Authorization::<Basic>::name()],
+            "Basic dXNlcjpzZWNyZXQ="
+        );
+
+        let header = headers.typed_get::<Authorization<Basic>>().unwrap();
+        assert_eq!(Credentials::from(header), credentials);
+    }
+}
@@ -0,0 +1,138 @@
+use crate::Hide;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Deref, DerefMut};
+
+/// Like [`Hide`], a value hidden from debug output. But one which can only be deserialized.
+///
+/// This type implements `Deserialize`, but deliberately not `Serialize`. So that values read
+/// from e.g. a configuration file can never be serialized again:
+///
+/// ```rust,compile_fail
+/// use hide::HideIn;
+///
+/// #[derive(serde::Serialize)]
+/// struct Config {
+///     password: HideIn<String>,
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(not(feature = "keyed-hash"), derive(Hash))]
+#[cfg_attr(not(feature = "keyed-ord"), derive(Ord, PartialOrd))]
+pub struct HideIn<T>(pub T);
+
+impl<T> HideIn<T> {
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    pub fn take(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for HideIn<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for HideIn<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> Display for HideIn<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        crate::write_substitute(f)
+    }
+}
+
+impl<T> Debug for HideIn<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        crate::write_substitute(f)
+    }
+}
+
+impl<T> From<T> for HideIn<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> From<HideIn<T>> for Hide<T> {
+    fn from(value: HideIn<T>) -> Self {
+        Hide(value.0)
+    }
+}
+
+impl<T> AsRef<T> for HideIn<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for HideIn<T>
+where
+    T: serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(Self(T::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl<T> schemars::JsonSchema for HideIn<T>
+where
+    T: schemars::JsonSchema,
+{
+    fn is_referenceable() -> bool {
+        T::is_referenceable()
+    }
+
+    fn schema_name() -> String {
+        T::schema_name()
+    }
+
+    fn schema_id() -> std::borrow::Cow<'static, str> {
+        T::schema_id()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        T::json_schema(gen)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_deserialize() {
+        #[allow(dead_code)]
+        #[derive(Debug, serde::Deserialize)]
+        struct Config {
+            username: String,
+            password: HideIn<String>,
+        }
+
+        let config: Config = serde_json::from_value(json!({
+            "username": "foo",
+            "password": "bar",
+        }))
+        .unwrap();
+
+        assert_eq!(config.password.as_str(), "bar");
+        assert_eq!(
+            format!("{config:?}"),
+            r#"Config { username: "foo", password: *** }"#
+        );
+    }
+}
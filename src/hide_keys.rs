@@ -0,0 +1,91 @@
+use crate::SUBSTITUTE;
+use std::fmt::{Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+
+/// Wraps a map, hiding its keys in the debug output, but showing the values.
+///
+/// This is the counterpart of [`SecretMap`](crate::SecretMap), for maps keyed by secrets, like
+/// rate limits by API key, where the values are worth seeing:
+///
+/// ```rust
+/// use hide::HideKeys;
+/// use std::collections::BTreeMap;
+///
+/// let mut requests = HideKeys(BTreeMap::new());
+/// requests.insert("sk-live-1234", 42);
+/// requests.insert("sk-live-5678", 7);
+///
+/// assert_eq!(format!("{requests:?}"), "{***: 42, ***: 7}");
+/// assert_eq!(requests.get("sk-live-1234"), Some(&42));
+/// ```
+///
+/// Works for all types which iterate over pairs of references, like [`HashMap`] and
+/// [`BTreeMap`].
+///
+/// [`HashMap`]: std::collections::HashMap
+/// [`BTreeMap`]: std::collections::BTreeMap
+#[derive(Clone, Default, Eq, PartialEq)]
+pub struct HideKeys<M>(pub M);
+
+impl<M> HideKeys<M> {
+    pub fn new(map: M) -> Self {
+        Self(map)
+    }
+
+    pub fn take(self) -> M {
+        self.0
+    }
+}
+
+impl<M> Deref for HideKeys<M> {
+    type Target = M;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<M> DerefMut for HideKeys<M> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<M> From<M> for HideKeys<M> {
+    fn from(value: M) -> Self {
+        Self(value)
+    }
+}
+
+impl<M, K, V> Debug for HideKeys<M>
+where
+    for<'a> &'a M: IntoIterator<Item = (&'a K, &'a V)>,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut map = f.debug_map();
+        for (_, value) in &self.0 {
+            map.entry(&format_args!("{SUBSTITUTE}"), value);
+        }
+        map.finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_debug() {
+        let buckets = HideKeys(HashMap::from([("secret", vec![1, 2])]));
+        assert_eq!(format!("{buckets:?}"), "{***: [1, 2]}");
+        assert_eq!(
+            format!("{buckets:#?}"),
+            "{\n    ***: [\n        1,\n        2,\n    ],\n}"
+        );
+
+        let empty: HideKeys<HashMap<String, u32>> = HideKeys::default();
+        assert_eq!(format!("{empty:?}"), "{}");
+    }
+}
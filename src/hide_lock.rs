@@ -0,0 +1,130 @@
+use crate::Hide;
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::{PoisonError, RwLock};
+
+/// A hidden value, which can be mutated through a shared reference.
+///
+/// Unlike `RwLock<Hide<T>>`, the debug output doesn't show the internals of the lock, and the
+/// value can only be accessed inside a closure, so that guards can't be kept around.
+///
+/// ```rust
+/// use hide::HideLock;
+///
+/// let token = HideLock::new("old".to_string());
+/// token.write_scoped(|token| *token = "new".to_string());
+///
+/// assert_eq!(token.read_scoped(|token| token.len()), 3);
+/// assert_eq!(format!("{token:?}"), "***");
+/// ```
+///
+/// A panic while holding the lock doesn't poison it, as the value is replaced as a whole in most
+/// cases.
+#[derive(Default)]
+pub struct HideLock<T>(RwLock<T>);
+
+impl<T> HideLock<T> {
+    pub fn new(value: T) -> Self {
+        Self(RwLock::new(value))
+    }
+
+    /// Run the closure with shared access to the value.
+    pub fn read_scoped<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        f(&self.0.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Run the closure with exclusive access to the value.
+    pub fn write_scoped<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        f(&mut self.0.write().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Replace the value, returning the old one.
+    pub fn replace(&self, value: T) -> Hide<T> {
+        Hide(self.write_scoped(|current| std::mem::replace(current, value)))
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        self.0.get_mut().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn into_inner(self) -> Hide<T> {
+        Hide(self.0.into_inner().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+impl<T> HideLock<T>
+where
+    T: Clone,
+{
+    /// Get a copy of the current value.
+    pub fn get(&self) -> Hide<T> {
+        Hide(self.read_scoped(T::clone))
+    }
+}
+
+impl<T> From<T> for HideLock<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> From<Hide<T>> for HideLock<T> {
+    fn from(value: Hide<T>) -> Self {
+        Self::new(value.0)
+    }
+}
+
+impl<T> Display for HideLock<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        crate::write_substitute(f)
+    }
+}
+
+impl<T> Debug for HideLock<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        crate::write_substitute(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Arc;
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    struct Client {
+        token: Arc<HideLock<String>>,
+    }
+
+    #[test]
+    fn test_lock() {
+        let client = Client {
+            token: Arc::new(HideLock::new("foo".to_string())),
+        };
+        assert_eq!(format!("{client:?}"), "Client { token: *** }");
+
+        let token = client.token.clone();
+        std::thread::spawn(move || token.write_scoped(|token| token.push_str("bar")))
+            .join()
+            .unwrap();
+
+        assert_eq!(client.token.get().as_str(), "foobar");
+        assert_eq!(client.token.replace("baz".to_string()).as_str(), "foobar");
+        assert_eq!(client.token.read_scoped(|token| token.clone()), "baz");
+    }
+
+    #[test]
+    fn test_poison() {
+        let token = Arc::new(HideLock::new(1));
+        let other = token.clone();
+        let _ = std::thread::spawn(move || other.write_scoped(|_| panic!())).join();
+
+        assert_eq!(token.read_scoped(|token| *token), 1);
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Deref, DerefMut};
+
+/// Like [`Hide`](crate::Hide), a value hidden from debug output. But one which can't be cloned.
+///
+/// This is intended for high-value keys, where creating copies should be impossible, even if
+/// the inner type implements [`Clone`]:
+///
+/// ```rust,compile_fail
+/// use hide::HideOnce;
+///
+/// let key = HideOnce(vec![1u8, 2, 3]);
+/// let copy: HideOnce<Vec<u8>> = key.clone();
+/// ```
+#[derive(Eq, PartialEq)]
+#[cfg_attr(not(feature = "keyed-hash"), derive(Hash))]
+#[cfg_attr(not(feature = "keyed-ord"), derive(Ord, PartialOrd))]
+pub struct HideOnce<T>(pub T);
+
+impl<T> HideOnce<T> {
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    pub fn take(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for HideOnce<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for HideOnce<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> Display for HideOnce<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        crate::write_substitute(f)
+    }
+}
+
+impl<T> Debug for HideOnce<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        crate::write_substitute(f)
+    }
+}
+
+impl<T> From<T> for HideOnce<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> AsRef<T> for HideOnce<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for HideOnce<T>
+where
+    T: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for HideOnce<T>
+where
+    T: serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(Self(T::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_debug() {
+        let key = HideOnce(vec![1u8, 2, 3]);
+        assert_eq!(format!("{key:?}"), "***");
+        assert_eq!(key.take(), vec![1u8, 2, 3]);
+    }
+}
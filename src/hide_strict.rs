@@ -0,0 +1,114 @@
+use crate::Hide;
+use std::fmt::{Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+
+/// Like [`Hide`], a value hidden from debug output. But one which doesn't implement
+/// [`Display`](std::fmt::Display).
+///
+/// Even printing `***` through [`Display`](std::fmt::Display) is most likely a mistake. With this
+/// type, it is a compile error:
+///
+/// ```rust,compile_fail
+/// use hide::HideStrict;
+///
+/// let secret = HideStrict("password".to_string());
+/// let output = format!("{}", secret);
+/// ```
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(not(feature = "keyed-hash"), derive(Hash))]
+#[cfg_attr(not(feature = "keyed-ord"), derive(Ord, PartialOrd))]
+pub struct HideStrict<T>(pub T);
+
+impl<T> HideStrict<T> {
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    pub fn take(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for HideStrict<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for HideStrict<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> Debug for HideStrict<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        crate::write_substitute(f)
+    }
+}
+
+impl<T> From<T> for HideStrict<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> From<Hide<T>> for HideStrict<T> {
+    fn from(value: Hide<T>) -> Self {
+        Self(value.0)
+    }
+}
+
+impl<T> From<HideStrict<T>> for Hide<T> {
+    fn from(value: HideStrict<T>) -> Self {
+        Self(value.0)
+    }
+}
+
+impl<T> AsRef<T> for HideStrict<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for HideStrict<T>
+where
+    T: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for HideStrict<T>
+where
+    T: serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(Self(T::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_debug() {
+        let secret = HideStrict("password".to_string());
+        assert_eq!(format!("{secret:?}"), "***");
+
+        let secret: Hide<String> = secret.into();
+        assert_eq!(secret.0, "password");
+    }
+}
@@ -0,0 +1,199 @@
+use crate::builder::{reserve, wipe};
+use crate::Hide;
+use std::fmt::{Debug, Display, Formatter, Write};
+use std::ops::Deref;
+
+/// A hidden string, which doesn't leave copies behind when being modified.
+///
+/// Mutating a `Hide<String>` through [`DerefMut`](std::ops::DerefMut) may reallocate the buffer,
+/// releasing the old one with the secret still in it. This type only allows appending and
+/// clearing. When the buffer needs to grow, the old buffer is wiped, so does dropping the string.
+///
+/// ```rust
+/// use hide::HideString;
+///
+/// let mut password = HideString::with_capacity(16);
+/// password.push_str("secret");
+/// password.push('!');
+///
+/// assert_eq!(format!("{password:?}"), "***");
+/// assert_eq!(password.as_str(), "secret!");
+/// ```
+#[derive(Clone, Default, Eq, PartialEq)]
+pub struct HideString {
+    buffer: String,
+}
+
+impl HideString {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty string, which can hold `capacity` bytes without growing.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buffer: String::with_capacity(capacity),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.buffer
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    /// Ensure capacity for at least `additional` more bytes, wiping the old buffer if it grows.
+    pub fn reserve(&mut self, additional: usize) {
+        reserve(&mut self.buffer, additional);
+    }
+
+    pub fn push_str(&mut self, s: &str) {
+        self.reserve(s.len());
+        self.buffer.push_str(s);
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.reserve(c.len_utf8());
+        self.buffer.push(c);
+    }
+
+    /// Wipe the content, keeping the capacity.
+    pub fn clear(&mut self) {
+        wipe(&mut self.buffer);
+    }
+
+    /// Convert into a `Hide<String>`, which no longer wipes its buffer when modified.
+    pub fn into_hide(mut self) -> Hide<String> {
+        Hide(std::mem::take(&mut self.buffer))
+    }
+}
+
+impl Drop for HideString {
+    fn drop(&mut self) {
+        wipe(&mut self.buffer);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for HideString {}
+
+impl Deref for HideString {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.buffer
+    }
+}
+
+impl AsRef<str> for HideString {
+    fn as_ref(&self) -> &str {
+        &self.buffer
+    }
+}
+
+impl AsRef<[u8]> for HideString {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_bytes()
+    }
+}
+
+impl Write for HideString {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
+
+impl Debug for HideString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        crate::write_substitute(f)
+    }
+}
+
+impl Display for HideString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        crate::write_substitute(f)
+    }
+}
+
+impl From<String> for HideString {
+    fn from(buffer: String) -> Self {
+        Self { buffer }
+    }
+}
+
+impl From<&str> for HideString {
+    fn from(value: &str) -> Self {
+        Self {
+            buffer: value.to_string(),
+        }
+    }
+}
+
+impl From<Hide<String>> for HideString {
+    fn from(value: Hide<String>) -> Self {
+        Self { buffer: value.0 }
+    }
+}
+
+impl From<HideString> for Hide<String> {
+    fn from(value: HideString) -> Self {
+        value.into_hide()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for HideString {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.buffer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for HideString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(Hide::<String>::deserialize(deserializer)?.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_grow() {
+        let mut value = HideString::with_capacity(1);
+        for _ in 0..100 {
+            value.push_str("ab");
+        }
+        write!(value, "{}", 42).unwrap();
+
+        assert_eq!(value.len(), 202);
+        assert!(value.capacity() >= 202);
+        assert!(value.ends_with("ab42"));
+        assert_eq!(format!("{value:?} {value}"), "*** ***");
+
+        let capacity = value.capacity();
+        value.clear();
+        assert!(value.is_empty());
+        assert_eq!(value.capacity(), capacity);
+    }
+
+    #[test]
+    fn test_convert() {
+        let value = HideString::from("secret");
+        let value: Hide<String> = value.into();
+        assert_eq!(value.0, "secret");
+
+        let value = HideString::from(value);
+        assert_eq!(value.as_str(), "secret");
+    }
+}
@@ -0,0 +1,135 @@
+use crate::Hide;
+use std::collections::{BTreeMap, HashMap};
+
+impl<T> Hide<Vec<T>> {
+    /// Iterate over the items, keeping each one hidden.
+    ///
+    /// ```rust
+    /// use hide::Hide;
+    ///
+    /// let keys = Hide::new(vec!["old".to_string(), "new".to_string()]);
+    /// let key = keys.iter_hidden().find(|key| key.as_str() == "new").unwrap();
+    /// assert_eq!(format!("{key:?}"), "***");
+    /// ```
+    pub fn iter_hidden(&self) -> impl Iterator<Item = Hide<&T>> {
+        self.0.iter().map(Hide)
+    }
+
+    /// Mutably iterate over the items, keeping each one hidden.
+    pub fn iter_hidden_mut(&mut self) -> impl Iterator<Item = Hide<&mut T>> {
+        self.0.iter_mut().map(Hide)
+    }
+
+    /// Consume the collection, keeping each item hidden.
+    pub fn into_iter_hidden(self) -> impl Iterator<Item = Hide<T>> {
+        self.0.into_iter().map(Hide)
+    }
+}
+
+/// Collect hidden items into a hidden collection.
+///
+/// ```rust
+/// use hide::Hide;
+///
+/// let tokens: Hide<Vec<String>> = "foo,bar"
+///     .split(',')
+///     .map(|token| Hide::new(token.to_string()))
+///     .collect();
+/// assert_eq!(tokens.len(), 2);
+/// ```
+impl<T> FromIterator<Hide<T>> for Hide<Vec<T>> {
+    fn from_iter<I: IntoIterator<Item = Hide<T>>>(iter: I) -> Self {
+        Hide(iter.into_iter().map(Hide::take).collect())
+    }
+}
+
+impl<T> Extend<Hide<T>> for Hide<Vec<T>> {
+    fn extend<I: IntoIterator<Item = Hide<T>>>(&mut self, iter: I) {
+        self.0.extend(iter.into_iter().map(Hide::take));
+    }
+}
+
+impl<K, V, S> Hide<HashMap<K, V, S>> {
+    /// Iterate over the entries, keeping the values hidden.
+    ///
+    /// The keys are not hidden, as they are considered to be labels.
+    pub fn iter_hidden(&self) -> impl Iterator<Item = (&K, Hide<&V>)> {
+        self.0.iter().map(|(k, v)| (k, Hide(v)))
+    }
+
+    /// Iterate over the values, keeping each one hidden.
+    pub fn values_hidden(&self) -> impl Iterator<Item = Hide<&V>> {
+        self.0.values().map(Hide)
+    }
+}
+
+impl<K, V> Hide<BTreeMap<K, V>> {
+    /// Iterate over the entries, in order of the keys, keeping the values hidden.
+    ///
+    /// The keys are not hidden, as they are considered to be labels.
+    pub fn iter_hidden(&self) -> impl Iterator<Item = (&K, Hide<&V>)> {
+        self.0.iter().map(|(k, v)| (k, Hide(v)))
+    }
+
+    /// Iterate over the values, in order of the keys, keeping each one hidden.
+    pub fn values_hidden(&self) -> impl Iterator<Item = Hide<&V>> {
+        self.0.values().map(Hide)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_vec() {
+        let mut keys = Hide(vec![1, 2, 3]);
+
+        assert_eq!(
+            format!("{:?}", keys.iter_hidden().collect::<Vec<_>>()),
+            "[***, ***, ***]"
+        );
+
+        for key in keys.iter_hidden_mut() {
+            *key.0 *= 2;
+        }
+        assert_eq!(
+            keys.into_iter_hidden().map(Hide::take).collect::<Vec<_>>(),
+            [2, 4, 6]
+        );
+    }
+
+    #[test]
+    fn test_collect() {
+        let mut shares: Hide<Vec<u8>> = [1, 2].into_iter().map(Hide).collect();
+        shares.extend([Hide(3)]);
+        assert_eq!(shares.0, [1, 2, 3]);
+
+        let doubled: Hide<Vec<u8>> = shares.into_iter_hidden().map(|v| Hide(v.0 * 2)).collect();
+        assert_eq!(doubled.0, [2, 4, 6]);
+    }
+
+    #[test]
+    fn test_map() {
+        let tokens = Hide(BTreeMap::from([("a", "foo"), ("b", "bar")]));
+
+        assert_eq!(
+            format!("{:?}", tokens.iter_hidden().collect::<Vec<_>>()),
+            r#"[("a", ***), ("b", ***)]"#
+        );
+        assert_eq!(
+            tokens.values_hidden().map(|v| *v.0).collect::<Vec<_>>(),
+            ["foo", "bar"]
+        );
+
+        let tokens = Hide(HashMap::from([("a", "foo")]));
+        assert_eq!(
+            tokens
+                .iter_hidden()
+                .map(|(k, v)| (*k, *v.0))
+                .collect::<Vec<_>>(),
+            [("a", "foo")]
+        );
+        assert_eq!(tokens.values_hidden().count(), 1);
+    }
+}
@@ -0,0 +1,66 @@
+//! Deriving keys from passwords, using Argon2
+//!
+//! The key is derived directly into the hidden result. `argon2` wipes its working memory, which
+//! contains intermediate values.
+
+use crate::Hide;
+use argon2::{Algorithm, Argon2, Error, Params, Version};
+
+impl<T: AsRef<[u8]>> Hide<T> {
+    /// Derive a key from the password, using Argon2id.
+    ///
+    /// The salt should be random, and unique per password, with at least 16 bytes. It isn't
+    /// secret, and needs to be stored alongside the encrypted data, like the parameters.
+    ///
+    /// ```rust
+    /// use argon2::Params;
+    /// use hide::Hide;
+    ///
+    /// let password = Hide::new("correct horse battery staple".to_string());
+    /// let key = password
+    ///     .derive_key_argon2(b"random and unique salt", Params::default())
+    ///     .unwrap();
+    /// assert_eq!(key.len(), 32);
+    /// ```
+    pub fn derive_key_argon2(&self, salt: &[u8], params: Params) -> Result<Hide<[u8; 32]>, Error> {
+        let mut key = Hide([0u8; 32]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params).hash_password_into(
+            self.0.as_ref(),
+            salt,
+            &mut key.0,
+        )?;
+        Ok(key)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn params() -> Params {
+        Params::new(64, 1, 1, None).unwrap()
+    }
+
+    #[test]
+    fn test_derive_key() {
+        let password = Hide("password".to_string());
+
+        let key = password.derive_key_argon2(b"somesalt", params()).unwrap();
+        assert_eq!(
+            key,
+            password.derive_key_argon2(b"somesalt", params()).unwrap()
+        );
+        assert_ne!(
+            key,
+            password.derive_key_argon2(b"othersalt", params()).unwrap()
+        );
+        assert_ne!(
+            key,
+            Hide("other")
+                .derive_key_argon2(b"somesalt", params())
+                .unwrap()
+        );
+
+        assert!(password.derive_key_argon2(b"short", params()).is_err());
+    }
+}
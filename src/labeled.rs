@@ -0,0 +1,138 @@
+use crate::Hide;
+use std::borrow::Cow;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Deref, DerefMut};
+
+/// A hidden value, carrying a label.
+///
+/// The label is shown in the debug output, the value is not:
+///
+/// ```rust
+/// use hide::Hide;
+///
+/// let password = Hide::<String>::labeled("db_password", "secret");
+/// assert_eq!(format!("{password:?}"), "<db_password:***>");
+/// ```
+#[derive(Hash, Eq, PartialEq)]
+#[cfg_attr(not(feature = "paranoid"), derive(Clone))]
+#[cfg_attr(not(feature = "keyed-ord"), derive(Ord, PartialOrd))]
+pub struct Labeled<T> {
+    label: Cow<'static, str>,
+    value: Hide<T>,
+}
+
+impl<T> Labeled<T> {
+    pub fn new(label: impl Into<Cow<'static, str>>, value: impl Into<Hide<T>>) -> Self {
+        Self {
+            label: label.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn value(&self) -> &Hide<T> {
+        &self.value
+    }
+
+    pub fn take(self) -> Hide<T> {
+        self.value
+    }
+}
+
+impl<T> Hide<T> {
+    /// Create a new hidden value, carrying a label.
+    pub fn labeled(label: impl Into<Cow<'static, str>>, value: impl Into<Hide<T>>) -> Labeled<T> {
+        Labeled::new(label, value)
+    }
+}
+
+impl<T> Deref for Labeled<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value.0
+    }
+}
+
+impl<T> DerefMut for Labeled<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value.0
+    }
+}
+
+impl<T> Display for Labeled<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<{}:{}>", self.label, crate::SUBSTITUTE)
+    }
+}
+
+impl<T> Debug for Labeled<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "policy")]
+impl<T: AsRef<[u8]>> Labeled<T> {
+    /// Render the value using the process-wide [`policy`](crate::policy), based on the label.
+    pub fn masked(&self) -> crate::policy::Masked<'_, Self> {
+        crate::policy::Masked(self)
+    }
+}
+
+#[cfg(feature = "policy")]
+impl<T: AsRef<[u8]>> Display for crate::policy::Masked<'_, Labeled<T>> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let value = crate::policy::apply(&self.0.label, self.0.value.0.as_ref());
+        write!(f, "<{}:{value}>", self.0.label)
+    }
+}
+
+#[cfg(feature = "policy")]
+impl<T: AsRef<[u8]>> Debug for crate::policy::Masked<'_, Labeled<T>> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl<T> From<Labeled<T>> for Hide<T> {
+    fn from(value: Labeled<T>) -> Self {
+        value.value
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    struct Example {
+        username: String,
+        password: Labeled<String>,
+        token: Labeled<String>,
+    }
+
+    #[test]
+    fn test_labeled() {
+        let ex = Example {
+            username: "foo".to_string(),
+            password: Hide::labeled("password", "bar"),
+            token: Hide::labeled(format!("token.{}", 1), "baz"),
+        };
+
+        assert_eq!(
+            format!("{ex:#?}"),
+            r#"Example {
+    username: "foo",
+    password: <password:***>,
+    token: <token.1:***>,
+}"#
+        );
+        assert_eq!(ex.token.label(), "token.1");
+        assert_eq!(ex.token.as_str(), "baz");
+    }
+}
@@ -0,0 +1,101 @@
+use crate::Hide;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Deref;
+use std::sync::OnceLock;
+
+/// A hidden value, which is initialized on first access.
+///
+/// This can be used for `static` items, so that global credentials are loaded exactly once:
+///
+/// ```rust
+/// use hide::LazyHide;
+///
+/// static TOKEN: LazyHide<String> = LazyHide::new(|| {
+///     std::env::var("API_TOKEN").unwrap_or_else(|_| "default".to_string())
+/// });
+///
+/// assert_eq!(format!("{TOKEN:?}"), "***");
+/// assert_eq!(TOKEN.as_str(), "default");
+/// ```
+///
+/// If multiple threads access the value at the same time, only one of them runs the
+/// initialization, the others wait for it to complete.
+pub struct LazyHide<T, F = fn() -> T> {
+    value: OnceLock<Hide<T>>,
+    init: F,
+}
+
+impl<T, F> LazyHide<T, F>
+where
+    F: Fn() -> T,
+{
+    pub const fn new(init: F) -> Self {
+        Self {
+            value: OnceLock::new(),
+            init,
+        }
+    }
+
+    /// Get the value, initializing it if this is the first access.
+    pub fn get(&self) -> &Hide<T> {
+        self.value.get_or_init(|| Hide((self.init)()))
+    }
+
+    /// Check if the value was already initialized.
+    pub fn is_initialized(&self) -> bool {
+        self.value.get().is_some()
+    }
+}
+
+impl<T, F> Deref for LazyHide<T, F>
+where
+    F: Fn() -> T,
+{
+    type Target = Hide<T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.get()
+    }
+}
+
+impl<T, F> Display for LazyHide<T, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        crate::write_substitute(f)
+    }
+}
+
+impl<T, F> Debug for LazyHide<T, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        crate::write_substitute(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    static TOKEN: LazyHide<String> = LazyHide::new(|| {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        "foo".to_string()
+    });
+
+    #[test]
+    fn test_lazy() {
+        assert!(!TOKEN.is_initialized());
+        assert_eq!(format!("{TOKEN:?}"), "***");
+
+        let threads = (0..4)
+            .map(|_| std::thread::spawn(|| TOKEN.len()))
+            .collect::<Vec<_>>();
+        for thread in threads {
+            assert_eq!(thread.join().unwrap(), 3);
+        }
+
+        assert!(TOKEN.is_initialized());
+        assert_eq!(TOKEN.get().as_str(), "foo");
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,163 @@
+//! Warnings about formatted secrets
+//!
+//! Formatting a [`Hide`](crate::Hide) only renders `***`, but code paths which routinely do so
+//! are good candidates for accessing the value explicitly, or not at all. Once enabled, each time
+//! a hidden value is formatted using [`Display`](std::fmt::Display) or [`Debug`](std::fmt::Debug),
+//! a `tracing` warning with the target `hide::leak` is emitted:
+//!
+//! ```rust
+//! use hide::{leak, Hide};
+//!
+//! # let production = true;
+//! if production {
+//!     leak::enable();
+//! }
+//!
+//! let password = Hide("secret");
+//! // warns: hidden value formatted type="&str" format="Debug" location="src/main.rs:10:1"
+//! println!("{password:?}");
+//! # leak::disable();
+//! ```
+//!
+//! This is meant for discovering code paths in production, so it shouldn't be enabled by tests,
+//! which format hidden values all the time. Values formatted by the `tracing` subscriber itself,
+//! as fields of events, are not reported, as `tracing` drops events emitted while handling one.
+//!
+//! The location of the caller is looked up from a backtrace, which requires debug info, and is
+//! `unknown` otherwise. As capturing a backtrace is slow, this should only be enabled temporarily.
+
+use std::backtrace::Backtrace;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// Set while reporting, so that formatting values in subscribers doesn't report again.
+    static REPORTING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Frames of these crates are skipped when looking up the caller.
+const SKIPPED: &[&str] = &[
+    "alloc::",
+    "core::",
+    "std::",
+    "hide::",
+    "log::",
+    "tracing::",
+    "tracing_core::",
+    "tracing_subscriber::",
+];
+
+/// Start warning about formatted hidden values.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Stop warning about formatted hidden values.
+pub fn disable() {
+    ENABLED.store(false, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Warn about a hidden value of type `T` being formatted, if enabled.
+pub(crate) fn report<T: ?Sized>(format: &'static str) {
+    if !is_enabled() || REPORTING.with(|reporting| reporting.replace(true)) {
+        return;
+    }
+
+    let backtrace = Backtrace::force_capture().to_string();
+    ::tracing::warn!(
+        target: "hide::leak",
+        r#type = crate::type_name::short::<T>(),
+        format,
+        location = caller(&backtrace).unwrap_or("unknown"),
+        "hidden value formatted"
+    );
+
+    REPORTING.with(|reporting| reporting.set(false));
+}
+
+/// Find the location of the first frame outside the standard library, logging crates, and this
+/// crate, in the rendered backtrace.
+///
+/// Frames are rendered as `  2: module::function`, followed by `at path:line:column`, if the
+/// location is known.
+fn caller(backtrace: &str) -> Option<&str> {
+    let mut skipped = true;
+    for line in backtrace.lines().map(str::trim) {
+        if let Some(location) = line.strip_prefix("at ") {
+            if !skipped {
+                return Some(location);
+            }
+        } else if let Some((_, function)) = line.split_once(": ") {
+            let function = function.trim_start_matches('<');
+            skipped = SKIPPED.iter().any(|skip| function.starts_with(skip));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Hide;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_caller() {
+        let backtrace = "   0: std::backtrace::Backtrace::force_capture
+             at /rustc/library/std/src/backtrace.rs:312:9
+   1: hide::leak::report
+             at ./src/leak.rs:70:21
+   2: <hide::Hide<T> as core::fmt::Debug>::fmt
+             at ./src/lib.rs:620:9
+   3: core::fmt::write
+   4: app::main
+             at ./src/main.rs:10:5
+   5: std::rt::lang_start";
+
+        assert_eq!(caller(backtrace), Some("./src/main.rs:10:5"));
+        assert_eq!(caller("   0: std::rt::lang_start"), None);
+    }
+
+    #[test]
+    fn test_report() {
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let writer = buffer.clone();
+        let subscriber = ::tracing_subscriber::fmt()
+            .with_writer(move || Capture(writer.clone()))
+            .with_ansi(false)
+            .finish();
+
+        let password = Hide("secret");
+        ::tracing::subscriber::with_default(subscriber, || {
+            enable();
+            let _ = password.to_string();
+            disable();
+            let _ = format!("{password:?}");
+        });
+
+        let logs = String::from_utf8(buffer.lock().unwrap().clone()).unwrap();
+        assert_eq!(logs.matches("hidden value formatted").count(), 1, "{logs}");
+        assert!(logs.contains(r#"type="&str" format="Display""#), "{logs}");
+        assert!(!logs.contains("secret"), "{logs}");
+    }
+}
@@ -0,0 +1,44 @@
+//! Integration with `lettre`
+//!
+//! The password is moved into the SMTP credentials, without copying it. Those don't wipe it, when
+//! being dropped.
+
+use crate::Credentials;
+use ::lettre::transport::smtp::authentication::Credentials as SmtpCredentials;
+
+impl Credentials {
+    /// Convert into credentials for SMTP authentication.
+    ///
+    /// ```rust
+    /// use hide::Credentials;
+    /// use lettre::SmtpTransport;
+    ///
+    /// let credentials = Credentials::new("admin", "secret");
+    /// let transport = SmtpTransport::builder_dangerous("localhost")
+    ///     .credentials(credentials.into_smtp())
+    ///     .build();
+    /// ```
+    pub fn into_smtp(self) -> SmtpCredentials {
+        SmtpCredentials::new(self.username, self.password.0)
+    }
+}
+
+impl From<Credentials> for SmtpCredentials {
+    fn from(value: Credentials) -> Self {
+        value.into_smtp()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_into_smtp() {
+        let credentials = SmtpCredentials::from(Credentials::new("admin", "secret"));
+        assert_eq!(
+            credentials,
+            SmtpCredentials::new("admin".into(), "secret".into())
+        );
+    }
+}
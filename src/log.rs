@@ -0,0 +1,200 @@
+//! Integration with `log`
+//!
+//! Log messages are scrubbed of all secrets registered with [`scrub::register`], either by
+//! wrapping a logger with [`ScrubLogger`]:
+//!
+//! ```rust
+//! use hide::log::ScrubLogger;
+//! # struct MyLogger;
+//! # impl log::Log for MyLogger {
+//! #     fn enabled(&self, _: &log::Metadata) -> bool { true }
+//! #     fn log(&self, _: &log::Record) {}
+//! #     fn flush(&self) {}
+//! # }
+//!
+//! log::set_boxed_logger(Box::new(ScrubLogger::new(MyLogger))).unwrap();
+//! ```
+//!
+//! Or, when using `env_logger`, by using [`format`] as formatter:
+//!
+//! ```rust
+//! # #[cfg(feature = "env_logger")]
+//! env_logger::Builder::from_default_env()
+//!     .format(hide::log::format)
+//!     .init();
+//! ```
+
+use crate::builder::wipe;
+use crate::scrub;
+use log::{Log, Metadata, Record};
+use std::borrow::Cow;
+
+/// A logger, scrubbing registered secrets from messages before passing them on.
+#[derive(Clone, Debug, Default)]
+pub struct ScrubLogger<L> {
+    inner: L,
+}
+
+impl<L: Log> ScrubLogger<L> {
+    pub fn new(inner: L) -> Self {
+        Self { inner }
+    }
+
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+}
+
+impl<L: Log> Log for ScrubLogger<L> {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.inner.enabled(record.metadata()) {
+            return;
+        }
+
+        with_scrubbed(record, |message| match message {
+            None => self.inner.log(record),
+            Some(message) => self.inner.log(
+                &Record::builder()
+                    .metadata(record.metadata().clone())
+                    .module_path(record.module_path())
+                    .file(record.file())
+                    .line(record.line())
+                    .args(format_args!("{message}"))
+                    .build(),
+            ),
+        });
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// A formatter for `env_logger`, scrubbing registered secrets from messages.
+///
+/// This writes records like the default format of `env_logger`, without colors:
+/// `[2024-05-01T12:00:00Z INFO  my_app] message`.
+#[cfg(feature = "env_logger")]
+pub fn format(buf: &mut env_logger::fmt::Formatter, record: &Record) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let timestamp = buf.timestamp();
+    with_scrubbed(record, |message| match message {
+        None => writeln!(
+            buf,
+            "[{timestamp} {:<5} {}] {}",
+            record.level(),
+            record.target(),
+            record.args()
+        ),
+        Some(message) => writeln!(
+            buf,
+            "[{timestamp} {:<5} {}] {message}",
+            record.level(),
+            record.target()
+        ),
+    })
+}
+
+/// Run the closure with the scrubbed message of the record, or `None` if it contains no secrets.
+fn with_scrubbed<R>(record: &Record, f: impl FnOnce(Option<&str>) -> R) -> R {
+    if record.args().as_str().is_some() {
+        // static messages can't contain secrets known at runtime
+        return f(None);
+    }
+
+    let mut message = record.args().to_string();
+    let result = match scrub::scrub(&message) {
+        Cow::Borrowed(_) => f(None),
+        Cow::Owned(scrubbed) => f(Some(&scrubbed)),
+    };
+    wipe(&mut message);
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Hide;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<String>>>);
+
+    impl Log for Capture {
+        fn enabled(&self, _: &Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record) {
+            self.0.lock().unwrap().push(record.args().to_string());
+        }
+
+        fn flush(&self) {}
+    }
+
+    fn record(logger: &impl Log, message: std::fmt::Arguments) {
+        logger.log(
+            &Record::builder()
+                .args(message)
+                .level(log::Level::Info)
+                .target("test")
+                .build(),
+        );
+    }
+
+    #[test]
+    fn test_logger() {
+        scrub::register(&Hide("hide-test-log"));
+
+        let capture = Capture::default();
+        let logger = ScrubLogger::new(capture.clone());
+        let token = "hide-test-log";
+        record(&logger, format_args!("token: {token}"));
+        record(&logger, format_args!("token: {}", 42));
+        record(&logger, format_args!("static"));
+
+        assert_eq!(
+            *capture.0.lock().unwrap(),
+            ["token: ***", "token: 42", "static"]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "env_logger")]
+    fn test_format() {
+        use std::io::{self, Write};
+
+        #[derive(Clone, Default)]
+        struct Output(Arc<Mutex<Vec<u8>>>);
+
+        impl Write for Output {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        scrub::register(&Hide("hide-test-env-logger"));
+
+        let output = Output::default();
+        let logger = env_logger::Builder::new()
+            .format(format)
+            .target(env_logger::Target::Pipe(Box::new(output.clone())))
+            .filter_level(log::LevelFilter::Info)
+            .build();
+        let token = "hide-test-env-logger";
+        record(&logger, format_args!("token: {token}"));
+
+        let output = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+        assert!(output.ends_with(" INFO  test] token: ***\n"), "{output}");
+    }
+}
@@ -0,0 +1,267 @@
+//! Reading credentials from `.netrc` files
+//!
+//! ```rust
+//! use hide::netrc::Netrc;
+//!
+//! let netrc = Netrc::parse("
+//! machine api.example.com
+//!   login admin
+//!   password secret
+//! default login anonymous password guest
+//! ").unwrap();
+//!
+//! let credentials = netrc.get("api.example.com").unwrap();
+//! assert_eq!(credentials.username, "admin");
+//! assert_eq!(credentials.password.as_str(), "secret");
+//!
+//! assert_eq!(netrc.get("example.com").unwrap().username, "anonymous");
+//! ```
+//!
+//! Missing logins and passwords are empty. Macro definitions (`macdef`) and `account` tokens are
+//! skipped, as are lines starting with `#`.
+
+use crate::builder::wipe;
+use crate::Credentials;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Failed to read a netrc file.
+///
+/// This never contains any part of the file.
+#[derive(Debug)]
+pub enum NetrcError {
+    /// Failed to read the file.
+    Io(io::Error),
+    /// The file is invalid, at the line.
+    Invalid { line: usize },
+}
+
+impl Display for NetrcError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read netrc: {err}"),
+            Self::Invalid { line } => write!(f, "invalid netrc at line {line}"),
+        }
+    }
+}
+
+impl std::error::Error for NetrcError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Invalid { .. } => None,
+        }
+    }
+}
+
+/// The credentials of a netrc file, by machine.
+#[derive(Debug, Default)]
+#[cfg_attr(not(feature = "paranoid"), derive(Clone))]
+pub struct Netrc {
+    machines: BTreeMap<String, Credentials>,
+    default: Option<Credentials>,
+}
+
+impl Netrc {
+    pub fn parse(netrc: &str) -> Result<Self, NetrcError> {
+        let mut result = Self::default();
+        let mut current: Option<(Option<String>, Credentials)> = None;
+        let mut tokens = Tokens::new(netrc);
+
+        while let Some((line, token)) = tokens.next() {
+            let mut value = || tokens.next().map(|(_, value)| value);
+            match token {
+                "machine" => {
+                    let name = value().ok_or(NetrcError::Invalid { line })?;
+                    result.insert(current.take());
+                    current = Some((Some(name.to_string()), Credentials::new("", "")));
+                }
+                "default" => {
+                    result.insert(current.take());
+                    current = Some((None, Credentials::new("", "")));
+                }
+                "login" | "password" | "account" => {
+                    let value = value().ok_or(NetrcError::Invalid { line })?;
+                    let Some((_, credentials)) = &mut current else {
+                        return Err(NetrcError::Invalid { line });
+                    };
+                    match token {
+                        "login" => credentials.username = value.to_string(),
+                        "password" => credentials.password.0 = value.to_string(),
+                        _ => {}
+                    }
+                }
+                "macdef" => {
+                    value().ok_or(NetrcError::Invalid { line })?;
+                    tokens.skip_macro();
+                }
+                _ => return Err(NetrcError::Invalid { line }),
+            }
+        }
+
+        result.insert(current);
+        Ok(result)
+    }
+
+    /// Read a netrc file, wiping the content afterwards.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, NetrcError> {
+        let mut netrc = std::fs::read_to_string(path).map_err(NetrcError::Io)?;
+        let result = Self::parse(&netrc);
+        wipe(&mut netrc);
+        result
+    }
+
+    /// Read the netrc file of the user, from the path in `NETRC`, or `.netrc` in the home
+    /// directory.
+    ///
+    /// If the file doesn't exist, the result is empty.
+    pub fn from_home() -> Result<Self, NetrcError> {
+        let path = match std::env::var_os("NETRC") {
+            Some(path) => PathBuf::from(path),
+            None => match std::env::home_dir() {
+                Some(home) => home.join(".netrc"),
+                None => return Ok(Self::default()),
+            },
+        };
+
+        match Self::load(path) {
+            Err(NetrcError::Io(err)) if err.kind() == io::ErrorKind::NotFound => {
+                Ok(Self::default())
+            }
+            result => result,
+        }
+    }
+
+    /// Get the credentials of the machine, or the default ones.
+    pub fn get(&self, machine: &str) -> Option<&Credentials> {
+        self.machines.get(machine).or(self.default.as_ref())
+    }
+
+    /// Iterate over the machines and their credentials, without the default ones.
+    pub fn machines(&self) -> impl Iterator<Item = (&str, &Credentials)> {
+        self.machines
+            .iter()
+            .map(|(machine, credentials)| (machine.as_str(), credentials))
+    }
+
+    /// Insert an entry, the first one for a machine wins.
+    fn insert(&mut self, entry: Option<(Option<String>, Credentials)>) {
+        match entry {
+            Some((Some(machine), credentials)) => {
+                self.machines.entry(machine).or_insert(credentials);
+            }
+            Some((None, credentials)) => {
+                self.default.get_or_insert(credentials);
+            }
+            None => {}
+        }
+    }
+}
+
+/// The tokens of a netrc file, with their line numbers.
+struct Tokens<'a> {
+    lines: std::iter::Enumerate<std::str::Lines<'a>>,
+    line: Option<(usize, std::str::SplitWhitespace<'a>)>,
+}
+
+impl<'a> Tokens<'a> {
+    fn new(netrc: &'a str) -> Self {
+        Self {
+            lines: netrc.lines().enumerate(),
+            line: None,
+        }
+    }
+
+    fn next(&mut self) -> Option<(usize, &'a str)> {
+        loop {
+            if let Some((number, tokens)) = &mut self.line {
+                if let Some(token) = tokens.next() {
+                    return Some((*number, token));
+                }
+            }
+
+            let (index, line) = self.lines.next()?;
+            self.line = match line.trim_start().starts_with('#') {
+                true => None,
+                false => Some((index + 1, line.split_whitespace())),
+            };
+        }
+    }
+
+    /// Skip the rest of the line, and the following lines up to an empty one.
+    fn skip_macro(&mut self) {
+        self.line = None;
+        for (_, line) in self.lines.by_ref() {
+            if line.trim().is_empty() {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let netrc = Netrc::parse(
+            "# comment
+machine a.example.com login a password secret-a
+machine b.example.com
+  account ignored
+  password secret-b
+
+macdef init
+  cd /pub
+  password not-a-token
+
+machine a.example.com login other password other
+machine c.example.com login c
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            netrc.get("a.example.com"),
+            Some(&Credentials::new("a", "secret-a"))
+        );
+        assert_eq!(
+            netrc.get("b.example.com"),
+            Some(&Credentials::new("", "secret-b"))
+        );
+        assert_eq!(netrc.get("c.example.com"), Some(&Credentials::new("c", "")));
+        assert_eq!(netrc.get("d.example.com"), None);
+        assert_eq!(
+            netrc
+                .machines()
+                .map(|(machine, _)| machine)
+                .collect::<Vec<_>>(),
+            ["a.example.com", "b.example.com", "c.example.com"]
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        let err = Netrc::parse("machine a\nlogin a\npassword").unwrap_err();
+        assert_eq!(err.to_string(), "invalid netrc at line 3");
+        assert!(matches!(
+            Netrc::parse("login a"),
+            Err(NetrcError::Invalid { line: 1 })
+        ));
+        assert!(matches!(
+            Netrc::parse("machine a secret"),
+            Err(NetrcError::Invalid { line: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_load() {
+        assert!(matches!(
+            Netrc::load("/does/not/exist"),
+            Err(NetrcError::Io(_))
+        ));
+    }
+}
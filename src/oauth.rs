@@ -0,0 +1,103 @@
+//! OAuth 2.0 token responses
+//!
+//! So that tokens are hidden right when deserializing the response of a token endpoint:
+//!
+//! ```rust
+//! use hide::oauth::TokenResponse;
+//!
+//! let response: TokenResponse = serde_json::from_str(r#"{
+//!     "access_token": "2YotnFZFEjr1zCsicMWpAA",
+//!     "token_type": "Bearer",
+//!     "expires_in": 3600,
+//!     "refresh_token": "tGzv3JOkF0XG5Qx2TlKWIA",
+//!     "scope": "read write"
+//! }"#).unwrap();
+//!
+//! assert_eq!(response.access_token.as_str(), "2YotnFZFEjr1zCsicMWpAA");
+//! assert_eq!(response.scopes().collect::<Vec<_>>(), ["read", "write"]);
+//! assert!(!format!("{response:?}").contains("2YotnFZFEjr1zCsicMWpAA"));
+//! ```
+
+use crate::Hide;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime};
+
+/// A successful response of a token endpoint, as defined by RFC 6749, section 5.1.
+///
+/// The `id_token` is added by OpenID Connect. Unknown fields are ignored.
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(not(feature = "paranoid"), derive(Clone))]
+pub struct TokenResponse {
+    pub access_token: Hide<String>,
+    pub token_type: String,
+    /// The lifetime of the access token, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_in: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<Hide<String>>,
+    /// The granted scopes, separated by spaces.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id_token: Option<Hide<String>>,
+}
+
+impl TokenResponse {
+    /// The lifetime of the access token.
+    pub fn expires_in(&self) -> Option<Duration> {
+        self.expires_in.map(Duration::from_secs)
+    }
+
+    /// The expiry of the access token, for a response which was received at the time.
+    pub fn expires_at(&self, received: SystemTime) -> Option<SystemTime> {
+        received.checked_add(self.expires_in()?)
+    }
+
+    /// Iterate over the granted scopes.
+    pub fn scopes(&self) -> impl Iterator<Item = &str> {
+        self.scope.iter().flat_map(|scope| scope.split_whitespace())
+    }
+
+    /// Check if the token type is `Bearer`, ignoring the case.
+    pub fn is_bearer(&self) -> bool {
+        self.token_type.eq_ignore_ascii_case("bearer")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_deserialize() {
+        let response: TokenResponse = serde_json::from_str(
+            r#"{"access_token": "secret", "token_type": "bearer", "extra": 1}"#,
+        )
+        .unwrap();
+
+        assert_eq!(response.access_token.as_str(), "secret");
+        assert!(response.is_bearer());
+        assert_eq!(response.expires_at(SystemTime::UNIX_EPOCH), None);
+        assert_eq!(response.scopes().count(), 0);
+        assert!(response.refresh_token.is_none());
+
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"access_token":"secret","token_type":"bearer"}"#
+        );
+    }
+
+    #[test]
+    fn test_expires() {
+        let response: TokenResponse = serde_json::from_str(
+            r#"{"access_token": "secret", "token_type": "mac", "expires_in": 60}"#,
+        )
+        .unwrap();
+
+        assert!(!response.is_bearer());
+        assert_eq!(
+            response.expires_at(SystemTime::UNIX_EPOCH),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(60))
+        );
+    }
+}
@@ -0,0 +1,100 @@
+//! One-time passwords, generated from a hidden shared secret
+//!
+//! ```rust
+//! use hide::{codec, Hide};
+//! use std::time::{Duration, SystemTime};
+//!
+//! let secret = codec::decode_base32(&Hide("GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ")).unwrap();
+//! let code = secret.totp(SystemTime::now(), 6, Duration::from_secs(30));
+//! assert_eq!(code.len(), 6);
+//! ```
+//!
+//! Codes are generated according to RFC 4226 (HOTP) and RFC 6238 (TOTP), using HMAC-SHA1, like
+//! common authenticator apps do. They are hidden as well, as they grant access while they are
+//! valid.
+
+use crate::Hide;
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+impl Hide<Vec<u8>> {
+    /// Generate the HMAC-based one-time password for the counter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `digits` is not between 1 and 10.
+    pub fn hotp(&self, counter: u64, digits: u32) -> Hide<String> {
+        assert!(
+            (1..=10).contains(&digits),
+            "digits must be between 1 and 10"
+        );
+
+        let mut mac = Hmac::<Sha1>::new_from_slice(&self.0).expect("any key length is valid");
+        mac.update(&counter.to_be_bytes());
+        let hash = mac.finalize().into_bytes();
+
+        let offset = (hash[hash.len() - 1] & 0xf) as usize;
+        let code = u32::from_be_bytes([
+            hash[offset],
+            hash[offset + 1],
+            hash[offset + 2],
+            hash[offset + 3],
+        ]) & 0x7fff_ffff;
+
+        let code = u64::from(code) % 10u64.pow(digits);
+        Hide(format!("{code:0width$}", width = digits as usize))
+    }
+
+    /// Generate the time-based one-time password for the time.
+    ///
+    /// Times before the Unix epoch are treated like the epoch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `digits` is not between 1 and 10, or if `period` is less than a second.
+    pub fn totp(&self, time: SystemTime, digits: u32, period: Duration) -> Hide<String> {
+        assert!(period.as_secs() > 0, "period must be at least a second");
+
+        let time = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        self.hotp(time.as_secs() / period.as_secs(), digits)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hotp() {
+        let secret = Hide(b"12345678901234567890".to_vec());
+        let expected = [
+            "755224", "287082", "359152", "969429", "338314", "254676", "287922", "162583",
+            "399871", "520489",
+        ];
+        for (counter, expected) in expected.into_iter().enumerate() {
+            assert_eq!(secret.hotp(counter as u64, 6).0, expected);
+        }
+    }
+
+    #[test]
+    fn test_totp() {
+        let secret = Hide(b"12345678901234567890".to_vec());
+        let period = Duration::from_secs(30);
+        for (time, expected) in [
+            (59, "94287082"),
+            (1111111109, "07081804"),
+            (1234567890, "89005924"),
+            (2000000000, "69279037"),
+        ] {
+            let time = UNIX_EPOCH + Duration::from_secs(time);
+            assert_eq!(secret.totp(time, 8, period).0, expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "digits must be between 1 and 10")]
+    fn test_digits() {
+        Hide(vec![1]).hotp(0, 0);
+    }
+}
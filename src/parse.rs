@@ -0,0 +1,125 @@
+use crate::{Hide, SUBSTITUTE};
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
+/// Parse a hidden value.
+///
+/// Errors are wrapped in a [`HiddenParseError`], as many of them include the input.
+impl<T> FromStr for Hide<T>
+where
+    T: FromStr,
+{
+    type Err = HiddenParseError<T::Err>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        T::from_str(s).map(Hide).map_err(|error| HiddenParseError {
+            error,
+            input: Hide(s.to_string()),
+        })
+    }
+}
+
+/// Failed to parse a hidden value.
+///
+/// This renders the message of the original error, replacing all occurrences of the input with
+/// `***`:
+///
+/// ```rust
+/// use hide::Hide;
+/// use std::str::FromStr;
+///
+/// #[derive(Debug)]
+/// struct Color;
+///
+/// impl FromStr for Color {
+///     type Err = String;
+///
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         Err(format!("unknown color: {s}"))
+///     }
+/// }
+///
+/// let err = "hunter2".parse::<Hide<Color>>().unwrap_err();
+/// assert_eq!(err.to_string(), "unknown color: ***");
+/// ```
+///
+/// Like for [`Hide<E>`](Hide), the original error isn't reported as the source, only its
+/// sources are.
+#[cfg_attr(not(feature = "paranoid"), derive(Clone))]
+pub struct HiddenParseError<E> {
+    error: E,
+    input: Hide<String>,
+}
+
+impl<E> HiddenParseError<E> {
+    /// The original error, e.g. to check its kind.
+    ///
+    /// Its message may include the input.
+    pub fn get_ref(&self) -> &E {
+        &self.error
+    }
+
+    /// Get the original error, see [`Self::get_ref`].
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+impl<E: Display> Display for HiddenParseError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let message = self.error.to_string();
+        match self.input.0.is_empty() {
+            true => f.write_str(&message),
+            false => f.write_str(&message.replace(self.input.0.as_str(), SUBSTITUTE)),
+        }
+    }
+}
+
+impl<E: Display> Debug for HiddenParseError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("HiddenParseError")
+            .field(&self.to_string())
+            .finish()
+    }
+}
+
+impl<E: std::error::Error> std::error::Error for HiddenParseError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error.source()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::num::IntErrorKind;
+
+    #[derive(Debug)]
+    struct Echo;
+
+    impl FromStr for Echo {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Err(format!("invalid value '{s}', expected 'foo'"))
+        }
+    }
+
+    #[test]
+    fn test_from_str() {
+        let pin: Hide<u16> = "1234".parse().unwrap();
+        assert_eq!(pin.0, 1234);
+
+        let err = "12x4".parse::<Hide<u16>>().unwrap_err();
+        assert_eq!(err.get_ref().kind(), &IntErrorKind::InvalidDigit);
+        assert_eq!(err.to_string(), "invalid digit found in string");
+
+        let err = "secret".parse::<Hide<Echo>>().unwrap_err();
+        assert_eq!(err.to_string(), "invalid value '***', expected 'foo'");
+        assert_eq!(
+            format!("{err:?}"),
+            r#"HiddenParseError("invalid value '***', expected 'foo'")"#
+        );
+        assert_eq!(err.into_inner(), "invalid value 'secret', expected 'foo'");
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{Hide, SUBSTITUTE};
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Deref, DerefMut};
+use std::path::{is_separator, Path, PathBuf, MAIN_SEPARATOR};
+
+impl From<&Path> for Hide<PathBuf> {
+    fn from(value: &Path) -> Self {
+        Hide(value.to_path_buf())
+    }
+}
+
+impl AsRef<Path> for Hide<PathBuf> {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for Hide<&Path> {
+    fn as_ref(&self) -> &Path {
+        self.0
+    }
+}
+
+impl Hide<PathBuf> {
+    /// Convert into a path, only hiding the file name.
+    pub fn hide_file_name(self) -> HideFileName<PathBuf> {
+        HideFileName(self.0)
+    }
+}
+
+/// Wraps a path, hiding only its file name from debug output.
+///
+/// For cases where the location of a file is sensitive, but knowing the directory helps with
+/// debugging:
+///
+/// ```rust
+/// use hide::HideFileName;
+///
+/// let key = HideFileName::new("/home/user/.keys/customer-a.pem");
+/// assert_eq!(format!("{key:?}"), "\"/home/user/.keys/***\"");
+/// assert_eq!(format!("{key}"), "/home/user/.keys/***");
+/// ```
+#[derive(Clone, Default, Eq, PartialEq)]
+#[cfg_attr(not(feature = "keyed-hash"), derive(Hash))]
+#[cfg_attr(not(feature = "keyed-ord"), derive(Ord, PartialOrd))]
+pub struct HideFileName<P = PathBuf>(pub P);
+
+impl<P> HideFileName<P> {
+    pub fn take(self) -> P {
+        self.0
+    }
+}
+
+impl HideFileName<PathBuf> {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self(path.into())
+    }
+}
+
+impl<P> Deref for HideFileName<P> {
+    type Target = P;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<P> DerefMut for HideFileName<P> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<P> AsRef<Path> for HideFileName<P>
+where
+    P: AsRef<Path>,
+{
+    fn as_ref(&self) -> &Path {
+        self.0.as_ref()
+    }
+}
+
+impl<P> From<HideFileName<P>> for Hide<P> {
+    fn from(value: HideFileName<P>) -> Self {
+        Hide(value.0)
+    }
+}
+
+impl<P> Display for HideFileName<P>
+where
+    P: AsRef<Path>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let path = self.0.as_ref();
+        match (path.parent(), path.file_name()) {
+            (Some(parent), Some(_)) if !parent.as_os_str().is_empty() => {
+                let parent = parent.as_os_str().to_string_lossy();
+                // the root, like `/`, already ends with a separator
+                match parent.ends_with(is_separator) {
+                    true => write!(f, "{parent}{SUBSTITUTE}"),
+                    false => write!(f, "{parent}{MAIN_SEPARATOR}{SUBSTITUTE}"),
+                }
+            }
+            _ => f.write_str(SUBSTITUTE),
+        }
+    }
+}
+
+impl<P> Debug for HideFileName<P>
+where
+    P: AsRef<Path>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.to_string(), f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_path() {
+        let path: Hide<PathBuf> = Path::new("/etc/secret.key").into();
+        assert_eq!(AsRef::<Path>::as_ref(&path), Path::new("/etc/secret.key"));
+        assert!(std::fs::metadata(&path).is_err());
+
+        let path = path.hide_file_name();
+        assert_eq!(path.to_string(), format!("/etc{MAIN_SEPARATOR}***"));
+    }
+
+    #[test]
+    fn test_file_name_only() {
+        assert_eq!(HideFileName::new("secret.key").to_string(), "***");
+        assert_eq!(HideFileName::new("/").to_string(), "***");
+    }
+
+    #[test]
+    fn test_root() {
+        assert_eq!(HideFileName::new("/secret.key").to_string(), "/***");
+    }
+}
@@ -0,0 +1,404 @@
+//! Masking of personally identifiable information
+//!
+//! Other than [`Hide`], which always replaces the value with a placeholder, the types in this
+//! module render a partially masked value, following a compliance [`Preset`].
+//!
+//! The preset can either be selected on the type level (e.g. `Pii<Gdpr>`), or for the whole
+//! process (using [`Global`] and [`set_global_preset`]).
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hide::pii::{Pii, PciDss};
+//!
+//! let card = Pii::<PciDss>::pan("4111 1111 1111 1234");
+//! assert_eq!(format!("{card:?}"), "411111***1234");
+//! ```
+
+use crate::{Hide, SUBSTITUTE};
+use std::fmt::{Debug, Display, Formatter};
+use std::marker::PhantomData;
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// The kind of information a [`Pii`] value holds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PiiKind {
+    /// An e-mail address
+    Email,
+    /// A primary account number (credit card number)
+    Pan,
+    /// A phone number
+    Phone,
+    /// Some kind of identification number (passport, social security number, …)
+    Id,
+    /// An IP address
+    Ip,
+}
+
+/// A compliance preset, defining how each [`PiiKind`] is masked.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum Preset {
+    /// Pseudonymise, keeping enough to correlate records (e-mail domain, network part of IPs).
+    #[default]
+    Gdpr,
+    /// Allow the first six and last four digits of a PAN, as permitted by PCI DSS.
+    PciDss,
+    /// Remove all identifiers (HIPAA "safe harbor").
+    Hipaa,
+}
+
+impl Preset {
+    /// Render a masked version of the value.
+    pub fn mask(&self, kind: PiiKind, value: &str, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self, kind) {
+            (Self::Hipaa, _) | (_, PiiKind::Id) => f.write_str(SUBSTITUTE),
+            (Self::Gdpr, PiiKind::Email) => mask_email(value, true, f),
+            (Self::PciDss, PiiKind::Email) => mask_email(value, false, f),
+            (Self::Gdpr, PiiKind::Pan) => mask_pan(value, 0, f),
+            (Self::PciDss, PiiKind::Pan) => mask_pan(value, 6, f),
+            (Self::Gdpr, PiiKind::Phone) => mask_tail(value, 2, f),
+            (Self::PciDss, PiiKind::Phone) => mask_tail(value, 4, f),
+            (_, PiiKind::Ip) => mask_ip(value, f),
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::PciDss,
+            2 => Self::Hipaa,
+            _ => Self::Gdpr,
+        }
+    }
+}
+
+/// Select a [`Preset`] on the type level.
+pub trait Compliance {
+    fn preset() -> Preset;
+}
+
+/// Type level selection of [`Preset::Gdpr`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Gdpr;
+
+/// Type level selection of [`Preset::PciDss`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PciDss;
+
+/// Type level selection of [`Preset::Hipaa`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Hipaa;
+
+/// Use the process wide preset, set by [`set_global_preset`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Global;
+
+impl Compliance for Gdpr {
+    fn preset() -> Preset {
+        Preset::Gdpr
+    }
+}
+
+impl Compliance for PciDss {
+    fn preset() -> Preset {
+        Preset::PciDss
+    }
+}
+
+impl Compliance for Hipaa {
+    fn preset() -> Preset {
+        Preset::Hipaa
+    }
+}
+
+impl Compliance for Global {
+    fn preset() -> Preset {
+        global_preset()
+    }
+}
+
+static GLOBAL_PRESET: AtomicU8 = AtomicU8::new(Preset::Gdpr as u8);
+
+/// Set the preset used by all [`Pii`] values selecting the [`Global`] preset.
+pub fn set_global_preset(preset: Preset) {
+    GLOBAL_PRESET.store(preset as u8, Ordering::Relaxed);
+}
+
+/// Get the preset used by all [`Pii`] values selecting the [`Global`] preset.
+pub fn global_preset() -> Preset {
+    Preset::from_u8(GLOBAL_PRESET.load(Ordering::Relaxed))
+}
+
+/// A piece of personally identifiable information, masked according to the preset `C`.
+#[derive(Hash, Eq, PartialEq)]
+#[cfg_attr(not(feature = "paranoid"), derive(Clone))]
+pub struct Pii<C = Global> {
+    kind: PiiKind,
+    value: Hide<String>,
+    _marker: PhantomData<C>,
+}
+
+impl<C> Pii<C> {
+    pub fn new(kind: PiiKind, value: impl Into<String>) -> Self {
+        Self {
+            kind,
+            value: Hide(value.into()),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn email(value: impl Into<String>) -> Self {
+        Self::new(PiiKind::Email, value)
+    }
+
+    pub fn pan(value: impl Into<String>) -> Self {
+        Self::new(PiiKind::Pan, value)
+    }
+
+    pub fn phone(value: impl Into<String>) -> Self {
+        Self::new(PiiKind::Phone, value)
+    }
+
+    pub fn id(value: impl Into<String>) -> Self {
+        Self::new(PiiKind::Id, value)
+    }
+
+    pub fn ip(value: impl Into<String>) -> Self {
+        Self::new(PiiKind::Ip, value)
+    }
+
+    pub fn kind(&self) -> PiiKind {
+        self.kind
+    }
+
+    /// Get the unmasked value, still wrapped in [`Hide`].
+    pub fn value(&self) -> &Hide<String> {
+        &self.value
+    }
+
+    pub fn take(self) -> Hide<String> {
+        self.value
+    }
+}
+
+impl<C: Compliance> Display for Pii<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        C::preset().mask(self.kind, &self.value.0, f)
+    }
+}
+
+impl<C: Compliance> Debug for Pii<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+/// A validated e-mail address, masked according to the preset `C`, like a [`Pii`] value.
+///
+/// ```rust
+/// use hide::pii::{HiddenEmail, PciDss};
+///
+/// let email = HiddenEmail::<PciDss>::parse("jane.doe@example.com").unwrap();
+/// assert_eq!(email.domain(), "example.com");
+/// assert_eq!(format!("{email:?}"), "***@example.com");
+///
+/// assert!(HiddenEmail::<PciDss>::parse("jane.doe").is_err());
+/// ```
+#[cfg(feature = "email_address")]
+#[derive(Hash, Eq, PartialEq)]
+#[cfg_attr(not(feature = "paranoid"), derive(Clone))]
+pub struct HiddenEmail<C = Global> {
+    value: Hide<email_address::EmailAddress>,
+    _marker: PhantomData<C>,
+}
+
+#[cfg(feature = "email_address")]
+impl<C> HiddenEmail<C> {
+    /// Parse and validate an e-mail address.
+    pub fn parse(value: &str) -> Result<Self, crate::HiddenParseError<email_address::Error>> {
+        Ok(Self {
+            value: value.parse()?,
+            _marker: PhantomData,
+        })
+    }
+
+    /// The domain, which isn't considered sensitive.
+    pub fn domain(&self) -> &str {
+        self.value.0.domain()
+    }
+
+    /// Get the unmasked local part, still wrapped in [`Hide`].
+    pub fn local_part(&self) -> Hide<&str> {
+        Hide(self.value.0.local_part())
+    }
+
+    /// Get the unmasked value, still wrapped in [`Hide`].
+    pub fn value(&self) -> &Hide<email_address::EmailAddress> {
+        &self.value
+    }
+
+    pub fn take(self) -> Hide<email_address::EmailAddress> {
+        self.value
+    }
+}
+
+#[cfg(feature = "email_address")]
+impl<C> std::str::FromStr for HiddenEmail<C> {
+    type Err = crate::HiddenParseError<email_address::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+#[cfg(feature = "email_address")]
+impl<C: Compliance> Display for HiddenEmail<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        C::preset().mask(PiiKind::Email, self.value.0.as_str(), f)
+    }
+}
+
+#[cfg(feature = "email_address")]
+impl<C: Compliance> Debug for HiddenEmail<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+fn mask_email(value: &str, keep_first: bool, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match value.rsplit_once('@') {
+        Some((local, domain)) if !local.is_empty() && !domain.is_empty() => {
+            if keep_first {
+                if let Some(c) = local.chars().next() {
+                    write!(f, "{c}")?;
+                }
+            }
+            write!(f, "{SUBSTITUTE}@{domain}")
+        }
+        _ => f.write_str(SUBSTITUTE),
+    }
+}
+
+fn mask_pan(value: &str, head: usize, f: &mut Formatter<'_>) -> std::fmt::Result {
+    let digits = value
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-'))
+        .collect::<Vec<_>>();
+
+    // too short, or not a number: don't reveal anything
+    if digits.len() < head + 4 + 2 || !digits.iter().all(char::is_ascii_digit) {
+        return f.write_str(SUBSTITUTE);
+    }
+
+    for c in &digits[..head] {
+        write!(f, "{c}")?;
+    }
+    f.write_str(SUBSTITUTE)?;
+    for c in &digits[digits.len() - 4..] {
+        write!(f, "{c}")?;
+    }
+    Ok(())
+}
+
+fn mask_tail(value: &str, tail: usize, f: &mut Formatter<'_>) -> std::fmt::Result {
+    let digits = value
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect::<Vec<_>>();
+
+    f.write_str(SUBSTITUTE)?;
+    if digits.len() > tail * 2 {
+        for c in &digits[digits.len() - tail..] {
+            write!(f, "{c}")?;
+        }
+    }
+    Ok(())
+}
+
+fn mask_ip(value: &str, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match value.parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => {
+            let [a, b, c, _] = ip.octets();
+            write!(f, "{a}.{b}.{c}.0")
+        }
+        Ok(IpAddr::V6(ip)) => {
+            let s = ip.segments();
+            write!(f, "{:x}:{:x}:{:x}::", s[0], s[1], s[2])
+        }
+        Err(_) => f.write_str(SUBSTITUTE),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gdpr() {
+        assert_eq!(
+            Pii::<Gdpr>::email("jane.doe@example.com").to_string(),
+            "j***@example.com"
+        );
+        assert_eq!(
+            Pii::<Gdpr>::pan("4111-1111-1111-1234").to_string(),
+            "***1234"
+        );
+        assert_eq!(Pii::<Gdpr>::phone("+49 170 1234567").to_string(), "***67");
+        assert_eq!(Pii::<Gdpr>::id("123-45-6789").to_string(), "***");
+        assert_eq!(Pii::<Gdpr>::ip("192.168.1.42").to_string(), "192.168.1.0");
+        assert_eq!(
+            Pii::<Gdpr>::ip("2001:db8:1:2::1").to_string(),
+            "2001:db8:1::"
+        );
+    }
+
+    #[test]
+    fn test_pci_dss() {
+        assert_eq!(
+            Pii::<PciDss>::email("jane.doe@example.com").to_string(),
+            "***@example.com"
+        );
+        assert_eq!(
+            Pii::<PciDss>::pan("4111 1111 1111 1234").to_string(),
+            "411111***1234"
+        );
+        assert_eq!(Pii::<PciDss>::pan("1234").to_string(), "***");
+        assert_eq!(
+            Pii::<PciDss>::phone("+49 170 1234567").to_string(),
+            "***4567"
+        );
+    }
+
+    #[test]
+    fn test_hipaa() {
+        assert_eq!(
+            Pii::<Hipaa>::email("jane.doe@example.com").to_string(),
+            "***"
+        );
+        assert_eq!(Pii::<Hipaa>::ip("192.168.1.42").to_string(), "***");
+    }
+
+    #[test]
+    fn test_malformed() {
+        assert_eq!(Pii::<Gdpr>::email("not an email").to_string(), "***");
+        assert_eq!(Pii::<Gdpr>::ip("localhost").to_string(), "***");
+        assert_eq!(Pii::<Gdpr>::pan("4111 abcd 1111 1234").to_string(), "***");
+    }
+
+    #[test]
+    #[cfg(feature = "email_address")]
+    fn test_hidden_email() {
+        let email = HiddenEmail::<Gdpr>::parse("jane.doe@example.com").unwrap();
+        assert_eq!(email.domain(), "example.com");
+        assert_eq!(*email.local_part(), "jane.doe");
+        assert_eq!(email.to_string(), "j***@example.com");
+        assert_eq!(email.take().0.as_str(), "jane.doe@example.com");
+
+        let email: HiddenEmail<Hipaa> = "jane.doe@example.com".parse().unwrap();
+        assert_eq!(email.to_string(), "***");
+
+        assert!(HiddenEmail::<Gdpr>::parse("jane.doe@").is_err());
+        assert!(HiddenEmail::<Gdpr>::parse("not an email").is_err());
+    }
+}
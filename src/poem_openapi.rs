@@ -0,0 +1,87 @@
+//! Integration with `poem-openapi`
+//!
+//! A `Hide<String>` is described as a string of the format `password`. It is rendered as `***`,
+//! and parse errors never contain the presented value.
+
+use crate::{Hide, SUBSTITUTE};
+use ::poem_openapi::registry::{MetaSchema, MetaSchemaRef};
+use ::poem_openapi::types::{ParseFromJSON, ParseFromParameter, ParseResult, ToJSON, Type};
+use serde_json::Value;
+use std::borrow::Cow;
+
+impl Type for Hide<String> {
+    const IS_REQUIRED: bool = true;
+
+    type RawValueType = String;
+
+    type RawElementValueType = String;
+
+    fn name() -> Cow<'static, str> {
+        "string_password".into()
+    }
+
+    fn schema_ref() -> MetaSchemaRef {
+        MetaSchemaRef::Inline(Box::new(MetaSchema::new_with_format("string", "password")))
+    }
+
+    fn as_raw_value(&self) -> Option<&Self::RawValueType> {
+        Some(&self.0)
+    }
+
+    fn raw_element_iter<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a Self::RawElementValueType> + 'a> {
+        Box::new(self.as_raw_value().into_iter())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl ParseFromJSON for Hide<String> {
+    fn parse_from_json(value: Option<Value>) -> ParseResult<Self> {
+        match value {
+            Some(Value::String(value)) => Ok(Hide(value)),
+            // the error of poem-openapi would contain the value
+            _ => Err("expected type \"string\"".into()),
+        }
+    }
+}
+
+impl ParseFromParameter for Hide<String> {
+    fn parse_from_parameter(value: &str) -> ParseResult<Self> {
+        Ok(Hide(value.to_string()))
+    }
+}
+
+impl ToJSON for Hide<String> {
+    fn to_json(&self) -> Option<Value> {
+        Some(Value::String(SUBSTITUTE.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_json() {
+        let value = Hide::<String>::parse_from_json(Some(json!("secret"))).unwrap();
+        assert_eq!(value.as_str(), "secret");
+        assert_eq!(value.to_json(), Some(json!("***")));
+
+        let err = Hide::<String>::parse_from_json(Some(json!(123456))).unwrap_err();
+        assert!(!err.into_message().contains("123456"));
+    }
+
+    #[test]
+    fn test_schema() {
+        let MetaSchemaRef::Inline(schema) = Hide::<String>::schema_ref() else {
+            panic!("expected inline schema");
+        };
+        assert_eq!(schema.ty, "string");
+        assert_eq!(schema.format, Some("password"));
+    }
+}
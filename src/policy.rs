@@ -0,0 +1,365 @@
+//! Masking rules, loaded from configuration
+//!
+//! By default, hidden values are always rendered as `***`. A [`Policy`] allows revealing a bit
+//! more about some of them, like the last four characters, or a hash, depending on their label.
+//! So that the rules can be tightened without changing code:
+//!
+//! ```rust
+//! use hide::{policy::{self, Policy}, Hide};
+//!
+//! let policy = Policy::from_toml(r#"
+//! [labels]
+//! stripe_key = "last4"
+//!
+//! [fields]
+//! token = "hash"
+//! "#).unwrap();
+//! policy::install(policy);
+//!
+//! let key = Hide::<String>::labeled("stripe_key", "sk_live_1234567890");
+//! assert_eq!(format!("{:?}", key.masked()), "<stripe_key:***7890>");
+//! assert_eq!(format!("{key:?}"), "<stripe_key:***>");
+//! ```
+//!
+//! A policy is applied when rendering [`Labeled`](crate::Labeled) values and
+//! [`SecretMap`](crate::SecretMap)s using their `masked` method, which requires the values to
+//! implement `AsRef<[u8]>`, and for secrets added to a [`Scrubber`](crate::scrub::Scrubber) using
+//! a label. Their [`Debug`](std::fmt::Debug) output stays `***`.
+
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Write};
+use std::io;
+use std::path::Path;
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
+
+static POLICY: RwLock<Option<Arc<Policy>>> = RwLock::new(None);
+
+/// The key for [`Mask::Hash`], random for each process, or `None` if it couldn't be created.
+static HASH_KEY: OnceLock<Option<[u8; 32]>> = OnceLock::new();
+
+fn hash_key() -> Option<&'static [u8; 32]> {
+    HASH_KEY
+        .get_or_init(|| {
+            let mut key = [0u8; 32];
+            getrandom::fill(&mut key).ok().map(|()| key)
+        })
+        .as_ref()
+}
+
+/// How to render a hidden value.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mask {
+    /// Only render `***`.
+    #[default]
+    Full,
+    /// Render the last four characters, e.g. `***7890`. Values with less than eight characters
+    /// are rendered as `***`.
+    Last4,
+    /// Render the first eight hexadecimal digits of an HMAC-SHA256 of the value, e.g.
+    /// `hmac:2bb80d53`.
+    ///
+    /// The key is random for each process, so values can be correlated within the logs of one
+    /// process, but the digest can neither be used to check guesses offline, nor be compared
+    /// across processes. If no random key can be created, this renders `***`.
+    Hash,
+    /// Render the number of characters, e.g. `*** (6 chars)`.
+    Length,
+    /// Render one `•` per character, e.g. `••••••`, like password fields do. Values with more
+    /// than [`MAX_BULLETS`] characters are rendered using that many.
+    Bullets,
+}
+
+/// The maximum number of characters rendered by [`Mask::Bullets`].
+pub const MAX_BULLETS: usize = 16;
+
+impl Mask {
+    /// Render the value.
+    ///
+    /// Values which are not valid UTF-8 are converted lossily first.
+    pub fn apply(&self, value: &[u8]) -> String {
+        match self {
+            Self::Full => crate::SUBSTITUTE.into(),
+            Self::Last4 => {
+                let value = String::from_utf8_lossy(value);
+                let len = value.chars().count();
+                match len < 8 {
+                    true => crate::SUBSTITUTE.into(),
+                    false => {
+                        let last: String = value.chars().skip(len - 4).collect();
+                        format!("{}{last}", crate::SUBSTITUTE)
+                    }
+                }
+            }
+            Self::Hash => {
+                let Some(key) = hash_key() else {
+                    return crate::SUBSTITUTE.into();
+                };
+                let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("any key length is valid");
+                mac.update(value);
+                let digest = mac.finalize().into_bytes();
+                let mut result = String::from("hmac:");
+                for byte in &digest[..4] {
+                    let _ = write!(result, "{byte:02x}");
+                }
+                result
+            }
+            Self::Length => {
+                let len = String::from_utf8_lossy(value).chars().count();
+                format!("{} ({len} chars)", crate::SUBSTITUTE)
+            }
+            Self::Bullets => {
+                let len = String::from_utf8_lossy(value).chars().count();
+                "•".repeat(len.min(MAX_BULLETS))
+            }
+        }
+    }
+}
+
+/// Failed to load a policy.
+#[derive(Debug)]
+pub enum PolicyError {
+    /// Failed to read the file.
+    Io(io::Error),
+    /// The JSON document is invalid.
+    Json(serde_json::Error),
+    /// The TOML document is invalid.
+    Toml(toml::de::Error),
+    /// The file has neither the extension `json`, nor `toml`.
+    UnknownFormat,
+}
+
+impl Display for PolicyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read policy: {err}"),
+            Self::Json(err) => write!(f, "invalid JSON policy: {err}"),
+            Self::Toml(err) => write!(f, "invalid TOML policy: {err}"),
+            Self::UnknownFormat => f.write_str("unknown policy format"),
+        }
+    }
+}
+
+impl std::error::Error for PolicyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Json(err) => Some(err),
+            Self::Toml(err) => Some(err),
+            Self::UnknownFormat => None,
+        }
+    }
+}
+
+/// Rules for masking hidden values, by their labels.
+///
+/// The mask for a label is looked up in `labels` first, which must match exactly. Then in
+/// `fields`, which match all labels containing them, ignoring the case. If several do, the
+/// longest one wins. Otherwise, the `default` is used, which is [`Mask::Full`] if not set.
+///
+/// In JSON, a policy looks like this:
+///
+/// ```json
+/// {
+///   "default": "full",
+///   "labels": { "stripe_key": "last4" },
+///   "fields": { "token": "hash", "password": "length" }
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Policy {
+    #[serde(default)]
+    default: Mask,
+    #[serde(default)]
+    labels: BTreeMap<String, Mask>,
+    #[serde(default)]
+    fields: BTreeMap<String, Mask>,
+}
+
+impl Policy {
+    /// Create a policy, using the mask for all labels.
+    pub fn new(default: Mask) -> Self {
+        Self {
+            default,
+            ..Default::default()
+        }
+    }
+
+    /// Use the mask for the label.
+    pub fn label(mut self, label: impl Into<String>, mask: Mask) -> Self {
+        self.labels.insert(label.into(), mask);
+        self
+    }
+
+    /// Use the mask for all labels containing the field name.
+    pub fn field(mut self, field: impl Into<String>, mask: Mask) -> Self {
+        self.fields.insert(field.into(), mask);
+        self
+    }
+
+    pub fn from_json(policy: &str) -> Result<Self, PolicyError> {
+        serde_json::from_str(policy).map_err(PolicyError::Json)
+    }
+
+    pub fn from_toml(policy: &str) -> Result<Self, PolicyError> {
+        toml::from_str(policy).map_err(PolicyError::Toml)
+    }
+
+    /// Read a policy from a file, with the extension `json` or `toml`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PolicyError> {
+        let path = path.as_ref();
+        let parse = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => Self::from_json,
+            Some("toml") => Self::from_toml,
+            _ => return Err(PolicyError::UnknownFormat),
+        };
+        parse(&std::fs::read_to_string(path).map_err(PolicyError::Io)?)
+    }
+
+    /// The mask for the label.
+    pub fn mask(&self, label: &str) -> Mask {
+        if let Some(mask) = self.labels.get(label) {
+            return *mask;
+        }
+
+        self.fields
+            .iter()
+            .filter(|(field, _)| crate::sensitive(&[field], label))
+            .max_by_key(|(field, _)| field.len())
+            .map_or(self.default, |(_, mask)| *mask)
+    }
+
+    /// Render the value, using the mask for the label.
+    pub fn apply(&self, label: &str, value: &[u8]) -> String {
+        self.mask(label).apply(value)
+    }
+}
+
+/// Install the process-wide policy, replacing the previous one.
+pub fn install(policy: Policy) {
+    *POLICY.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(policy));
+}
+
+/// Remove the process-wide policy, so that all values are rendered as `***` again.
+pub fn uninstall() {
+    *POLICY.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// Get the process-wide policy, if one is installed.
+pub fn installed() -> Option<Arc<Policy>> {
+    POLICY
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// Renders a value using the process-wide policy, see [`Labeled::masked`] and
+/// [`SecretMap::masked`].
+///
+/// [`Labeled::masked`]: crate::Labeled::masked
+/// [`SecretMap::masked`]: crate::SecretMap::masked
+pub struct Masked<'a, T: ?Sized>(pub(crate) &'a T);
+
+/// Render the value, using the process-wide policy.
+///
+/// If no policy is installed, this is always `***`.
+pub fn apply(label: &str, value: &[u8]) -> String {
+    match installed() {
+        Some(policy) => policy.apply(label, value),
+        None => Mask::Full.apply(value),
+    }
+}
+
+/// Serializes tests using the process-wide policy.
+#[cfg(test)]
+pub(crate) static TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_mask() {
+        let value = b"sk_live_1234567890";
+        assert_eq!(Mask::Full.apply(value), "***");
+        assert_eq!(Mask::Last4.apply(value), "***7890");
+        assert_eq!(Mask::Last4.apply(b"1234567"), "***");
+        let hash = Mask::Hash.apply(b"secret");
+        assert!(hash.starts_with("hmac:"));
+        assert_eq!(hash.len(), 13);
+        assert_eq!(Mask::Hash.apply(b"secret"), hash);
+        assert_ne!(Mask::Hash.apply(b"secrets"), hash);
+        assert_eq!(Mask::Length.apply("äöü".as_bytes()), "*** (3 chars)");
+        assert_eq!(Mask::Bullets.apply("äöü".as_bytes()), "•••");
+        assert_eq!(Mask::Bullets.apply(value), "•".repeat(MAX_BULLETS));
+    }
+
+    #[test]
+    fn test_policy() {
+        let policy = Policy::from_json(
+            r#"{
+                "labels": { "api_token": "last4" },
+                "fields": { "token": "hash", "refresh_token": "length", "pin": "bullets" }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(policy.mask("api_token"), Mask::Last4);
+        assert_eq!(policy.mask("GitHub_Token"), Mask::Hash);
+        assert_eq!(policy.mask("oauth.refresh_token"), Mask::Length);
+        assert_eq!(policy.mask("card_pin"), Mask::Bullets);
+        assert_eq!(policy.mask("password"), Mask::Full);
+
+        let policy = Policy::from_toml("default = \"length\"\n[labels]\nkey = \"full\"").unwrap();
+        assert_eq!(policy.mask("key"), Mask::Full);
+        assert_eq!(policy.mask("password"), Mask::Length);
+
+        assert!(Policy::from_json(r#"{"default": "none"}"#).is_err());
+        assert!(Policy::from_toml("rules = []").is_err());
+        assert!(matches!(
+            Policy::load("policy.yaml"),
+            Err(PolicyError::UnknownFormat)
+        ));
+    }
+
+    #[test]
+    fn test_installed() {
+        let _lock = TEST_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        install(
+            Policy::default()
+                .label("policy_test_key", Mask::Last4)
+                .field("policy_test_token", Mask::Length),
+        );
+
+        let key = crate::Hide::<String>::labeled("policy_test_key", "sk_live_1234567890");
+        assert_eq!(key.masked().to_string(), "<policy_test_key:***7890>");
+        assert_eq!(key.to_string(), "<policy_test_key:***>");
+
+        let mut secrets: crate::SecretMap = crate::SecretMap::new();
+        secrets.insert("policy_test_token", "secret");
+        secrets.insert("password", "secret");
+        assert_eq!(
+            format!("{:?}", secrets.masked()),
+            r#"{"password": ***, "policy_test_token": *** (6 chars)}"#
+        );
+        assert_eq!(
+            format!("{secrets:?}"),
+            r#"{"password": ***, "policy_test_token": ***}"#
+        );
+
+        let scrubber = crate::scrub::Scrubber::new()
+            .with_labeled("policy_test_key", &crate::Hide("sk_live_1234567890"))
+            .with(&crate::Hide("secret"));
+        assert_eq!(
+            scrubber.scrub("key=sk_live_1234567890 password=secret"),
+            "key=***7890 password=***"
+        );
+
+        uninstall();
+        assert_eq!(key.masked().to_string(), "<policy_test_key:***>");
+    }
+}
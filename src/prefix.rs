@@ -0,0 +1,85 @@
+//! Reveal a short prefix of the value, if the formatter has a precision set.
+
+use crate::SUBSTITUTE;
+use std::fmt::{Display, Formatter, Write};
+
+/// Write the first `precision` characters of the value, followed by `…***`.
+///
+/// If the value isn't longer than the precision, nothing is revealed.
+pub(crate) fn fmt<T: Display>(
+    value: &T,
+    precision: usize,
+    f: &mut Formatter<'_>,
+) -> std::fmt::Result {
+    let mut counter = Truncate::new(Discard, 0);
+    write!(counter, "{value}")?;
+
+    if counter.seen <= precision {
+        return crate::write_substitute(f);
+    }
+
+    write!(Truncate::new(&mut *f, precision), "{value}")?;
+    write!(f, "…{SUBSTITUTE}")
+}
+
+/// Forward the first `limit` characters, and count all of them.
+struct Truncate<W> {
+    inner: W,
+    limit: usize,
+    seen: usize,
+}
+
+impl<W> Truncate<W> {
+    fn new(inner: W, limit: usize) -> Self {
+        Self {
+            inner,
+            limit,
+            seen: 0,
+        }
+    }
+}
+
+impl<W: Write> Write for Truncate<W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        let remaining = self.limit.saturating_sub(self.seen);
+        self.seen += s.chars().count();
+
+        match s.char_indices().nth(remaining) {
+            Some((n, _)) => self.inner.write_str(&s[..n]),
+            None => self.inner.write_str(s),
+        }
+    }
+}
+
+struct Discard;
+
+impl Write for Discard {
+    fn write_str(&mut self, _: &str) -> std::fmt::Result {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Hide;
+
+    #[test]
+    fn test_prefix() {
+        let key = Hide::new("sk-live-1234567890".to_string());
+
+        assert_eq!(format!("{key}"), "***");
+        assert_eq!(format!("{key:.4}"), "sk-l…***");
+        assert_eq!(format!("{key:.0}"), "…***");
+    }
+
+    #[test]
+    fn test_short() {
+        assert_eq!(format!("{:.4}", Hide::new("abcd")), "***");
+        assert_eq!(format!("{:.4}", Hide::new("abcde")), "abcd…***");
+    }
+
+    #[test]
+    fn test_unicode() {
+        assert_eq!(format!("{:.2}", Hide::new("äöü")), "äö…***");
+    }
+}
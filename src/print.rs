@@ -0,0 +1,92 @@
+//! Support for the [`println_redacted!`](crate::println_redacted) and
+//! [`eprintln_redacted!`](crate::eprintln_redacted) macros.
+
+use std::fmt::Arguments;
+use std::io::{self, Write};
+
+/// Print to the standard output, with a newline, scrubbing all registered secrets.
+///
+/// Uses the same syntax as [`println!`]. The message is formatted as usual, and all secrets
+/// registered with [`scrub::register`](crate::scrub::register) are replaced before it is written:
+///
+/// ```rust
+/// use hide::{println_redacted, Hide};
+///
+/// let token = Hide::new("secret".to_string());
+/// hide::scrub::register(&token);
+///
+/// // prints "request failed: invalid token ***"
+/// println_redacted!("request failed: invalid token {}", token.0);
+/// ```
+///
+/// This is meant for diagnostics of command line tools, which may contain values derived from
+/// secrets by accident. Secrets which are not registered are printed as is. The unscrubbed message
+/// is wiped afterwards.
+#[macro_export]
+macro_rules! println_redacted {
+    () => {
+        ::std::println!()
+    };
+    ($($arg:tt)+) => {
+        $crate::__private::println_redacted(::core::format_args!($($arg)+))
+    };
+}
+
+/// Print to the standard error, with a newline, scrubbing all registered secrets.
+///
+/// This is the counterpart of [`println_redacted!`](crate::println_redacted), writing to the
+/// standard error like [`eprintln!`].
+#[macro_export]
+macro_rules! eprintln_redacted {
+    () => {
+        ::std::eprintln!()
+    };
+    ($($arg:tt)+) => {
+        $crate::__private::eprintln_redacted(::core::format_args!($($arg)+))
+    };
+}
+
+pub fn println_redacted(args: Arguments<'_>) {
+    if let Err(err) = write_redacted(io::stdout().lock(), args) {
+        panic!("failed printing to stdout: {err}");
+    }
+}
+
+pub fn eprintln_redacted(args: Arguments<'_>) {
+    if let Err(err) = write_redacted(io::stderr().lock(), args) {
+        panic!("failed printing to stderr: {err}");
+    }
+}
+
+fn write_redacted(mut out: impl Write, args: Arguments<'_>) -> io::Result<()> {
+    let mut message = std::fmt::format(args);
+    let result = writeln!(out, "{}", crate::scrub::scrub(&message));
+    crate::builder::wipe(&mut message);
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Hide;
+
+    #[test]
+    fn test_write_redacted() {
+        crate::scrub::register(&Hide("print-redacted-secret"));
+
+        let mut out = Vec::new();
+        write_redacted(
+            &mut out,
+            format_args!("token {}, id {}", "print-redacted-secret", 42),
+        )
+        .unwrap();
+        assert_eq!(out, b"token ***, id 42\n");
+    }
+
+    #[test]
+    fn test_macros() {
+        println_redacted!();
+        println_redacted!("{}", "print-redacted-other");
+        eprintln_redacted!("{:?}", Some(1),);
+    }
+}
@@ -0,0 +1,43 @@
+use crate::Hide;
+use std::io;
+
+impl Hide<String> {
+    /// Prompt for a password on the terminal, without echoing the input.
+    ///
+    /// The value is read from the TTY, not from stdin, and the trailing newline is removed.
+    ///
+    /// ```rust,no_run
+    /// use hide::Hide;
+    ///
+    /// let password = Hide::<String>::prompt("Password: ")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn prompt(prompt: impl ToString) -> io::Result<Self> {
+        rpassword::prompt_password(prompt).map(Hide)
+    }
+
+    /// Like [`Self::prompt`], but using a custom configuration, e.g. for reading from a
+    /// different input.
+    pub fn prompt_with_config(
+        prompt: impl ToString,
+        config: rpassword::Config,
+    ) -> io::Result<Self> {
+        rpassword::prompt_password_with_config(prompt, config).map(Hide)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_prompt() {
+        let config = rpassword::ConfigBuilder::new()
+            .input_reader(Cursor::new("secret\n"))
+            .output_writer(Cursor::new(Vec::new()))
+            .build();
+        let password = Hide::<String>::prompt_with_config("Password: ", config).unwrap();
+        assert_eq!(password.0, "secret");
+    }
+}
@@ -0,0 +1,121 @@
+//! Integration with `prost`
+//!
+//! A hidden message is encoded like the inner message. As `prost` implements `Message` for the
+//! well known wrapper types, `Hide<String>` and `Hide<Vec<u8>>` can be used as message fields,
+//! which are encoded as `google.protobuf.StringValue` and `google.protobuf.BytesValue`. The
+//! derived `Debug` implementation of the message then renders them as `***`:
+//!
+//! ```rust
+//! use hide::Hide;
+//!
+//! #[derive(Clone, PartialEq, prost::Message)]
+//! struct Login {
+//!     #[prost(string, tag = "1")]
+//!     username: String,
+//!     #[prost(message, optional, tag = "2")]
+//!     password: Option<Hide<String>>,
+//! }
+//!
+//! let login = Login {
+//!     username: "admin".into(),
+//!     password: Some(Hide("secret".into())),
+//! };
+//! assert!(!format!("{login:?}").contains("secret"));
+//! ```
+
+use crate::Hide;
+use ::prost::bytes::{Buf, BufMut};
+use ::prost::encoding::{DecodeContext, WireType};
+use ::prost::{DecodeError, Message};
+
+impl<M: Message> Message for Hide<M> {
+    fn encode_raw(&self, buf: &mut impl BufMut) {
+        self.0.encode_raw(buf)
+    }
+
+    fn merge_field(
+        &mut self,
+        tag: u32,
+        wire_type: WireType,
+        buf: &mut impl Buf,
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError> {
+        self.0.merge_field(tag, wire_type, buf, ctx)
+    }
+
+    fn encoded_len(&self) -> usize {
+        self.0.encoded_len()
+    }
+
+    fn clear(&mut self) {
+        self.0.clear()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Clone, PartialEq, Message)]
+    struct Credentials {
+        #[prost(string, tag = "1")]
+        username: String,
+        #[prost(message, optional, tag = "2")]
+        password: Option<Hide<String>>,
+        #[prost(message, optional, tag = "3")]
+        key: Option<Hide<Vec<u8>>>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    struct Plain {
+        #[prost(string, tag = "1")]
+        username: String,
+        #[prost(message, optional, tag = "2")]
+        password: Option<String>,
+        #[prost(message, optional, tag = "3")]
+        key: Option<Vec<u8>>,
+    }
+
+    #[test]
+    fn test_encode() {
+        let credentials = Credentials {
+            username: "admin".into(),
+            password: Some(Hide("secret".into())),
+            key: Some(Hide(vec![1, 2, 3])),
+        };
+        let plain = Plain {
+            username: "admin".into(),
+            password: Some("secret".into()),
+            key: Some(vec![1, 2, 3]),
+        };
+
+        let encoded = credentials.encode_to_vec();
+        assert_eq!(encoded, plain.encode_to_vec());
+        assert_eq!(Credentials::decode(&*encoded).unwrap(), credentials);
+    }
+
+    #[test]
+    fn test_debug() {
+        #[derive(Clone, PartialEq, Message)]
+        struct Request {
+            #[prost(message, optional, tag = "1")]
+            credentials: Option<Hide<Credentials>>,
+        }
+
+        let credentials = Credentials {
+            username: "admin".into(),
+            password: Some(Hide("secret".into())),
+            key: None,
+        };
+        assert_eq!(
+            format!("{credentials:?}"),
+            r#"Credentials { username: "admin", password: Some(***), key: None }"#
+        );
+
+        let request = Request {
+            credentials: Some(Hide(credentials)),
+        };
+        let decoded = Request::decode(&*request.encode_to_vec()).unwrap();
+        assert_eq!(format!("{decoded:?}"), "Request { credentials: Some(***) }");
+    }
+}
@@ -0,0 +1,148 @@
+//! Load hidden values from secret stores
+//!
+//! Secrets are loaded by name using a [`SecretProvider`], or an [`AsyncSecretProvider`] for
+//! stores which are accessed over the network. So that applications are written against one
+//! interface, and the backend is chosen per environment:
+//!
+//! ```rust
+//! use hide::{providers::{Env, Files, SecretProvider}, Hide};
+//! use std::io;
+//!
+//! fn provider() -> Box<dyn SecretProvider<Error = io::Error>> {
+//!     match std::env::var_os("KUBERNETES_SERVICE_HOST") {
+//!         Some(_) => Box::new(Files::new("/var/run/secrets/app")),
+//!         None => Box::new(Env::with_prefix("APP_")),
+//!     }
+//! }
+//!
+//! # std::env::set_var("APP_DB_PASSWORD", "secret");
+//! # std::env::remove_var("KUBERNETES_SERVICE_HOST");
+//! let password = provider().get("DB_PASSWORD")?;
+//! # assert_eq!(password.as_str(), "secret");
+//! # Ok::<(), io::Error>(())
+//! ```
+//!
+//! In Kubernetes, the token of the service account is loaded using a [`ServiceAccountToken`],
+//! which reloads it once rotated.
+//!
+//! Remote stores are behind a feature of the same name.
+
+use crate::Hide;
+#[cfg(any(feature = "aws", feature = "gcp", feature = "vault"))]
+use crate::SecretMap;
+#[cfg(any(feature = "aws", feature = "gcp", feature = "vault"))]
+use serde_json::{Map, Value};
+#[cfg(any(feature = "aws", feature = "gcp", feature = "vault"))]
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+
+#[cfg(feature = "aws")]
+pub mod aws;
+mod cached;
+mod env;
+mod files;
+#[cfg(feature = "gcp")]
+pub mod gcp;
+mod kubernetes;
+#[cfg(feature = "vault")]
+pub mod vault;
+
+pub use cached::CachedProvider;
+pub use env::Env;
+pub use files::Files;
+pub use kubernetes::ServiceAccountToken;
+
+/// A secret store, getting secrets by name.
+pub trait SecretProvider {
+    type Error;
+
+    /// Get the string value of a secret.
+    fn get(&self, name: &str) -> Result<Hide<String>, Self::Error>;
+}
+
+/// A secret store, getting secrets by name asynchronously, like remote secret stores.
+pub trait AsyncSecretProvider {
+    type Error;
+
+    /// Get the string value of a secret.
+    fn get(&self, name: &str) -> impl Future<Output = Result<Hide<String>, Self::Error>> + Send;
+}
+
+/// The secret is not a valid JSON object.
+///
+/// This never contains any part of the secret.
+#[cfg(any(feature = "aws", feature = "gcp", feature = "vault"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidJson;
+
+#[cfg(any(feature = "aws", feature = "gcp", feature = "vault"))]
+impl Display for InvalidJson {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("secret is not a valid JSON object")
+    }
+}
+
+#[cfg(any(feature = "aws", feature = "gcp", feature = "vault"))]
+impl std::error::Error for InvalidJson {}
+
+/// Expand a secret containing a JSON object into its fields.
+///
+/// String fields are used as they are, all other fields as their JSON representation.
+///
+/// ```rust
+/// use hide::{Hide, providers};
+///
+/// let secret = Hide::new(r#"{"username":"admin","password":"secret","port":5432}"#);
+/// let secrets = providers::expand_json(&secret).unwrap();
+///
+/// assert_eq!(format!("{secrets:?}"), r#"{"password": ***, "port": ***, "username": ***}"#);
+/// assert_eq!(secrets.get("port").unwrap().as_str(), "5432");
+/// ```
+#[cfg(any(feature = "aws", feature = "gcp", feature = "vault"))]
+pub fn expand_json<T: AsRef<str>>(secret: &Hide<T>) -> Result<SecretMap, InvalidJson> {
+    // the error of serde_json may contain parts of the input, so it must be dropped
+    let fields: Map<String, Value> =
+        serde_json::from_str(secret.0.as_ref()).map_err(|_| InvalidJson)?;
+
+    Ok(expand_object(fields))
+}
+
+#[cfg(any(feature = "aws", feature = "gcp", feature = "vault"))]
+pub(crate) fn expand_object(fields: Map<String, Value>) -> SecretMap {
+    fields
+        .into_iter()
+        .map(|(label, value)| match value {
+            Value::String(value) => (label, value),
+            value => (label, value.to_string()),
+        })
+        .collect()
+}
+
+#[cfg(any(feature = "aws", feature = "gcp", feature = "vault"))]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_expand_json() {
+        let secrets = expand_json(&Hide(
+            r#"{"user":"admin","password":"secret","port":5432,"tls":{"verify":true}}"#,
+        ))
+        .unwrap();
+
+        assert_eq!(
+            secrets.labels().collect::<Vec<_>>(),
+            ["password", "port", "tls", "user"]
+        );
+        assert_eq!(secrets.get("password").unwrap().as_str(), "secret");
+        assert_eq!(secrets.get("port").unwrap().as_str(), "5432");
+        assert_eq!(secrets.get("tls").unwrap().as_str(), r#"{"verify":true}"#);
+    }
+
+    #[test]
+    fn test_expand_invalid() {
+        assert_eq!(expand_json(&Hide("secret")), Err(InvalidJson));
+        assert_eq!(expand_json(&Hide(r#"["secret"]"#)), Err(InvalidJson));
+        assert_eq!(InvalidJson.to_string(), "secret is not a valid JSON object");
+    }
+}
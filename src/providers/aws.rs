@@ -0,0 +1,96 @@
+//! Load secrets from AWS Secrets Manager
+//!
+//! The configuration, credentials and region are taken from the environment, see
+//! [`aws_config::load_defaults`]. To re-use a client, use the `*_with` variants.
+//!
+//! ```rust,no_run
+//! use hide::providers::aws;
+//!
+//! # async fn example() -> Result<(), aws::Error> {
+//! let token = aws::secret("prod/api/token").await?;
+//!
+//! // a secret containing a JSON object, like `{"username":"admin","password":"secret"}`
+//! let database = aws::secret_map("prod/db").await?;
+//! let password = database.get("password");
+//! # Ok(())
+//! # }
+//! ```
+
+use super::expand_json;
+use crate::{Hide, SecretMap};
+use aws_config::BehaviorVersion;
+use aws_sdk_secretsmanager::Client;
+use std::fmt::{Display, Formatter};
+
+/// Failed to load a secret.
+#[derive(Debug)]
+pub enum Error {
+    /// Failed to fetch the secret.
+    Fetch(Box<aws_sdk_secretsmanager::Error>),
+    /// The secret has no string value, e.g. because it is a binary secret.
+    NotString,
+    /// The secret is not a valid JSON object.
+    InvalidJson,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fetch(err) => write!(f, "failed to fetch secret: {err}"),
+            Self::NotString => f.write_str("secret has no string value"),
+            Self::InvalidJson => f.write_str("secret is not a valid JSON object"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Fetch(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+async fn client() -> Client {
+    Client::new(&aws_config::load_defaults(BehaviorVersion::latest()).await)
+}
+
+/// Fetch the string value of a secret, by name or ARN.
+pub async fn secret(name: &str) -> Result<Hide<String>, Error> {
+    secret_with(&client().await, name).await
+}
+
+/// Fetch a secret containing a JSON object, expanding its fields into labeled entries.
+///
+/// See [`expand_json`](super::expand_json) for how fields are converted.
+pub async fn secret_map(name: &str) -> Result<SecretMap, Error> {
+    secret_map_with(&client().await, name).await
+}
+
+/// Fetch the string value of a secret, using an existing client.
+pub async fn secret_with(client: &Client, name: &str) -> Result<Hide<String>, Error> {
+    let output = client
+        .get_secret_value()
+        .secret_id(name)
+        .send()
+        .await
+        .map_err(|err| Error::Fetch(Box::new(err.into())))?;
+
+    output.secret_string.map(Hide).ok_or(Error::NotString)
+}
+
+/// Get secrets by name or ARN, using [`secret_with`].
+impl super::AsyncSecretProvider for Client {
+    type Error = Error;
+
+    async fn get(&self, name: &str) -> Result<Hide<String>, Error> {
+        secret_with(self, name).await
+    }
+}
+
+/// Fetch a secret containing a JSON object, using an existing client.
+pub async fn secret_map_with(client: &Client, name: &str) -> Result<SecretMap, Error> {
+    let secret = secret_with(client, name).await?;
+    expand_json(&secret).map_err(|_| Error::InvalidJson)
+}
@@ -0,0 +1,279 @@
+use super::{AsyncSecretProvider, SecretProvider};
+use crate::builder::wipe;
+use crate::Hide;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Cache the secrets of a provider, so that remote stores aren't accessed for every request,
+/// while rotated secrets are still picked up after the TTL.
+///
+/// Once the TTL of a secret has passed, it is still used for the `stale` period (which is zero by
+/// default): one caller refreshes it, while concurrent callers get the stale value. If refreshing
+/// fails, the stale value is used as well.
+///
+/// Cached values are wiped when they are replaced, invalidated, or the cache is dropped.
+///
+/// ```rust
+/// use hide::providers::{CachedProvider, Env, SecretProvider};
+/// use std::time::Duration;
+///
+/// let provider = CachedProvider::new(Env::with_prefix("APP_"), Duration::from_secs(300))
+///     .stale(Duration::from_secs(60))
+///     .ttl_for("SESSION_KEY", Duration::from_secs(30));
+///
+/// # std::env::set_var("APP_DB_PASSWORD", "secret");
+/// let password = provider.get("DB_PASSWORD")?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct CachedProvider<P> {
+    inner: P,
+    ttl: Duration,
+    stale: Duration,
+    ttls: HashMap<String, Duration>,
+    cache: Cache,
+}
+
+/// The cached entries, wiped when dropped.
+#[derive(Default)]
+struct Cache(Mutex<HashMap<String, Entry>>);
+
+impl Cache {
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Entry>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn clear(&self) {
+        for (_, mut entry) in self.lock().drain() {
+            wipe(&mut entry.value.0);
+        }
+    }
+}
+
+impl Drop for Cache {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+struct Entry {
+    value: Hide<String>,
+    /// The end of the TTL, `None` if it never ends.
+    fresh_until: Option<Instant>,
+    /// The end of the stale period.
+    stale_until: Option<Instant>,
+    refreshing: bool,
+}
+
+enum Lookup {
+    /// Use the cached value.
+    Cached(Hide<String>),
+    /// Get the value from the inner provider, falling back to the stale value on errors.
+    Get(Option<Hide<String>>),
+}
+
+fn before(now: Instant, until: Option<Instant>) -> bool {
+    until.is_none_or(|until| now < until)
+}
+
+impl<P> CachedProvider<P> {
+    /// Cache the secrets of the provider, for the TTL.
+    pub fn new(inner: P, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            stale: Duration::ZERO,
+            ttls: HashMap::new(),
+            cache: Cache::default(),
+        }
+    }
+
+    /// Keep using secrets for the period after their TTL, while they are being refreshed.
+    pub fn stale(mut self, stale: Duration) -> Self {
+        self.stale = stale;
+        self
+    }
+
+    /// Use a different TTL for the secret.
+    pub fn ttl_for(mut self, name: impl Into<String>, ttl: Duration) -> Self {
+        self.ttls.insert(name.into(), ttl);
+        self
+    }
+
+    /// Remove the secret from the cache, so that it is fetched again on the next access.
+    pub fn invalidate(&self, name: &str) {
+        if let Some(mut entry) = self.cache.lock().remove(name) {
+            wipe(&mut entry.value.0);
+        }
+    }
+
+    /// Remove all secrets from the cache.
+    pub fn clear(&self) {
+        self.cache.clear();
+    }
+
+    /// Get the inner provider, wiping the cached secrets.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+
+    fn lookup(&self, name: &str) -> Lookup {
+        let now = Instant::now();
+        let mut cache = self.cache.lock();
+        match cache.get_mut(name) {
+            Some(entry) if before(now, entry.fresh_until) => {
+                Lookup::Cached(Hide(entry.value.0.clone()))
+            }
+            Some(entry) if before(now, entry.stale_until) => match entry.refreshing {
+                true => Lookup::Cached(Hide(entry.value.0.clone())),
+                false => {
+                    entry.refreshing = true;
+                    Lookup::Get(Some(Hide(entry.value.0.clone())))
+                }
+            },
+            _ => Lookup::Get(None),
+        }
+    }
+
+    fn store<E>(
+        &self,
+        name: &str,
+        result: Result<Hide<String>, E>,
+        stale: Option<Hide<String>>,
+    ) -> Result<Hide<String>, E> {
+        let mut cache = self.cache.lock();
+        match result {
+            Ok(value) => {
+                let ttl = self.ttls.get(name).copied().unwrap_or(self.ttl);
+                let fresh_until = Instant::now().checked_add(ttl);
+                let entry = Entry {
+                    value: Hide(value.0.clone()),
+                    fresh_until,
+                    stale_until: fresh_until.and_then(|fresh| fresh.checked_add(self.stale)),
+                    refreshing: false,
+                };
+                if let Some(mut entry) = cache.insert(name.to_string(), entry) {
+                    wipe(&mut entry.value.0);
+                }
+                Ok(value)
+            }
+            Err(err) => match stale {
+                Some(stale) => {
+                    if let Some(entry) = cache.get_mut(name) {
+                        entry.refreshing = false;
+                    }
+                    Ok(stale)
+                }
+                None => Err(err),
+            },
+        }
+    }
+}
+
+impl<P: Debug> Debug for CachedProvider<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedProvider")
+            .field("inner", &self.inner)
+            .field("ttl", &self.ttl)
+            .field("stale", &self.stale)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P: SecretProvider> SecretProvider for CachedProvider<P> {
+    type Error = P::Error;
+
+    fn get(&self, name: &str) -> Result<Hide<String>, P::Error> {
+        match self.lookup(name) {
+            Lookup::Cached(value) => Ok(value),
+            Lookup::Get(stale) => self.store(name, self.inner.get(name), stale),
+        }
+    }
+}
+
+impl<P> AsyncSecretProvider for CachedProvider<P>
+where
+    P: AsyncSecretProvider + Sync,
+{
+    type Error = P::Error;
+
+    async fn get(&self, name: &str) -> Result<Hide<String>, P::Error> {
+        match self.lookup(name) {
+            Lookup::Cached(value) => Ok(value),
+            Lookup::Get(stale) => {
+                let result = self.inner.get(name).await;
+                self.store(name, result, stale)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CachedProvider, Duration, Hide, SecretProvider};
+    use crate::providers::{AsyncSecretProvider, Env};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Returns the number of calls, failing after the first one, if `fail` is set.
+    #[derive(Default)]
+    struct Counter {
+        calls: AtomicUsize,
+        fail: bool,
+    }
+
+    impl SecretProvider for Counter {
+        type Error = ();
+
+        fn get(&self, name: &str) -> Result<Hide<String>, ()> {
+            let calls = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            match self.fail && calls > 1 {
+                true => Err(()),
+                false => Ok(Hide(format!("{name}{calls}"))),
+            }
+        }
+    }
+
+    #[test]
+    fn test_ttl() {
+        let provider = CachedProvider::new(Counter::default(), Duration::from_secs(3600))
+            .ttl_for("token", Duration::ZERO);
+
+        assert_eq!(provider.get("password").unwrap().0, "password1");
+        assert_eq!(provider.get("password").unwrap().0, "password1");
+        assert_eq!(provider.get("token").unwrap().0, "token2");
+        assert_eq!(provider.get("token").unwrap().0, "token3");
+
+        provider.invalidate("password");
+        assert_eq!(provider.get("password").unwrap().0, "password4");
+        assert_eq!(provider.into_inner().calls.into_inner(), 4);
+    }
+
+    #[test]
+    fn test_stale() {
+        let counter = Counter {
+            fail: true,
+            ..Default::default()
+        };
+        let provider =
+            CachedProvider::new(counter, Duration::ZERO).stale(Duration::from_secs(3600));
+        assert_eq!(provider.get("password").unwrap().0, "password1");
+        assert_eq!(provider.get("password").unwrap().0, "password1");
+
+        provider.clear();
+        assert_eq!(provider.get("password"), Err(()));
+    }
+
+    #[tokio::test]
+    async fn test_async() {
+        let provider = CachedProvider::new(Env::new(), Duration::from_secs(3600));
+
+        std::env::set_var("HIDE_TEST_CACHED_PROVIDER", "secret");
+        let value = AsyncSecretProvider::get(&provider, "HIDE_TEST_CACHED_PROVIDER").await;
+        assert_eq!(value.unwrap().0, "secret");
+
+        std::env::set_var("HIDE_TEST_CACHED_PROVIDER", "rotated");
+        let value = AsyncSecretProvider::get(&provider, "HIDE_TEST_CACHED_PROVIDER").await;
+        assert_eq!(value.unwrap().0, "secret");
+    }
+}
@@ -0,0 +1,77 @@
+use super::{AsyncSecretProvider, SecretProvider};
+use crate::Hide;
+use std::io;
+
+/// Get secrets from environment variables, optionally with a prefix.
+///
+/// Errors never contain the value of the variable.
+#[derive(Clone, Debug, Default)]
+pub struct Env {
+    prefix: String,
+}
+
+impl Env {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prepend the prefix to the name of the secret, e.g. `APP_` for `APP_DB_PASSWORD`.
+    pub fn with_prefix(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+        }
+    }
+}
+
+impl SecretProvider for Env {
+    type Error = io::Error;
+
+    fn get(&self, name: &str) -> io::Result<Hide<String>> {
+        let name = format!("{}{name}", self.prefix);
+        match std::env::var_os(&name).map(|value| value.into_string()) {
+            Some(Ok(value)) => Ok(Hide(value)),
+            Some(Err(_)) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("environment variable {name} is not valid unicode"),
+            )),
+            None => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("environment variable {name} is not set"),
+            )),
+        }
+    }
+}
+
+/// Reading environment variables doesn't block, so this is the same as [`SecretProvider`].
+impl AsyncSecretProvider for Env {
+    type Error = io::Error;
+
+    async fn get(&self, name: &str) -> io::Result<Hide<String>> {
+        SecretProvider::get(self, name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Env, SecretProvider};
+    use std::io;
+
+    #[test]
+    fn test_get() {
+        std::env::set_var("HIDE_TEST_ENV_PROVIDER", "secret");
+
+        let env = Env::with_prefix("HIDE_TEST_");
+        assert_eq!(env.get("ENV_PROVIDER").unwrap().0, "secret");
+        assert_eq!(
+            Env::new().get("HIDE_TEST_ENV_PROVIDER").unwrap().0,
+            "secret"
+        );
+
+        let err = env.get("ENV_PROVIDER_UNSET").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert_eq!(
+            err.to_string(),
+            "environment variable HIDE_TEST_ENV_PROVIDER_UNSET is not set"
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use super::SecretProvider;
+use crate::Hide;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Fetch secrets from files in a directory, like Docker or Kubernetes secrets mounted to
+/// `/run/secrets`.
+///
+/// The name of a secret is the name of the file. Names which aren't a plain file name, like
+/// `../secret` or `db/password`, are rejected. The content is used as it is, including any
+/// trailing newline.
+#[derive(Clone, Debug)]
+pub struct Files {
+    dir: PathBuf,
+}
+
+impl Files {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Fetch Docker secrets, from `/run/secrets`.
+    pub fn docker() -> Self {
+        Self::new("/run/secrets")
+    }
+
+    fn path(&self, name: &str) -> io::Result<PathBuf> {
+        let mut components = Path::new(name).components();
+        match (components.next(), components.next()) {
+            (Some(Component::Normal(_)), None) => Ok(self.dir.join(name)),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "secret name must be a file name",
+            )),
+        }
+    }
+}
+
+impl SecretProvider for Files {
+    type Error = io::Error;
+
+    fn get(&self, name: &str) -> io::Result<Hide<String>> {
+        Hide::<String>::from_file(self.path(name)?)
+    }
+}
+
+/// Reads the files without blocking the runtime.
+#[cfg(feature = "tokio")]
+impl super::AsyncSecretProvider for Files {
+    type Error = io::Error;
+
+    async fn get(&self, name: &str) -> io::Result<Hide<String>> {
+        Hide::<String>::from_file_async(self.path(name)?).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("hide-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("db_password"), "secret\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_get() {
+        let dir = dir("files");
+
+        let files = Files::new(&dir);
+        assert_eq!(files.get("db_password").unwrap().0, "secret\n");
+        for name in [
+            "missing",
+            "../db_password",
+            "db/password",
+            "/etc/passwd",
+            "",
+        ] {
+            assert!(files.get(name).is_err());
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_get_async() {
+        let dir = dir("files-async");
+
+        let files = Files::new(&dir);
+        let password = crate::providers::AsyncSecretProvider::get(&files, "db_password").await;
+        assert_eq!(password.unwrap().0, "secret\n");
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,229 @@
+//! Load secrets from Google Cloud Secret Manager
+//!
+//! Secrets are referenced by the full name of a version, like
+//! `projects/my-project/secrets/db-password/versions/latest`, see [`latest`].
+//!
+//! ```rust,no_run
+//! use hide::providers::gcp::{self, Client};
+//!
+//! # async fn example() -> Result<(), gcp::Error> {
+//! // using the service account of the workload
+//! let token = gcp::secret(&gcp::latest("my-project", "api-token")).await?;
+//!
+//! // re-using a client, and expanding a secret containing a JSON object
+//! let client = Client::workload_identity().await?;
+//! let database = client
+//!     .secret_map("projects/my-project/secrets/db/versions/3")
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::expand_json;
+use crate::{codec, Hide, SecretMap};
+use reqwest::header::HeaderValue;
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+
+const API: &str = "https://secretmanager.googleapis.com/v1";
+const METADATA_TOKEN: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+
+/// Failed to load a secret.
+#[derive(Debug)]
+pub enum Error {
+    /// Failed to perform the request.
+    Request(reqwest::Error),
+    /// The server responded with an error status.
+    Status(u16),
+    /// The token can't be used as a header value.
+    InvalidToken,
+    /// The response doesn't have the expected structure.
+    InvalidResponse,
+    /// The secret is not valid UTF-8.
+    NotString,
+    /// The secret is not a valid JSON object.
+    InvalidJson,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Request(err) => write!(f, "failed to perform request: {err}"),
+            Self::Status(status) => write!(f, "request failed with status: {status}"),
+            Self::InvalidToken => f.write_str("invalid token"),
+            Self::InvalidResponse => f.write_str("invalid response"),
+            Self::NotString => f.write_str("secret is not valid UTF-8"),
+            Self::InvalidJson => f.write_str("secret is not a valid JSON object"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Request(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        Self::Request(err)
+    }
+}
+
+/// The name of the latest version of a secret.
+pub fn latest(project: &str, secret: &str) -> String {
+    format!("projects/{project}/secrets/{secret}/versions/latest")
+}
+
+/// Fetch the string value of a secret version, using workload identity.
+pub async fn secret(name: &str) -> Result<Hide<String>, Error> {
+    Client::workload_identity().await?.secret(name).await
+}
+
+/// Fetch a secret version containing a JSON object, using workload identity.
+///
+/// See [`expand_json`](super::expand_json) for how fields are converted.
+pub async fn secret_map(name: &str) -> Result<SecretMap, Error> {
+    Client::workload_identity().await?.secret_map(name).await
+}
+
+/// A client, authenticated with an OAuth access token.
+#[derive(Debug)]
+#[cfg_attr(not(feature = "paranoid"), derive(Clone))]
+pub struct Client {
+    http: reqwest::Client,
+    token: Hide<String>,
+}
+
+impl Client {
+    /// Create a new client, using an access token.
+    pub fn new(token: Hide<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            token,
+        }
+    }
+
+    /// Create a new client, using the access token of the service account of the workload.
+    ///
+    /// The token is requested from the metadata server, which is available on GKE (with
+    /// workload identity), Cloud Run, Compute Engine, and similar environments.
+    pub async fn workload_identity() -> Result<Self, Error> {
+        let http = reqwest::Client::new();
+        let response = send(http.get(METADATA_TOKEN).header("Metadata-Flavor", "Google")).await?;
+
+        Ok(Self {
+            http,
+            token: parse_token(response)?,
+        })
+    }
+
+    /// Fetch the value of a secret version.
+    pub async fn secret_bytes(&self, name: &str) -> Result<Hide<Vec<u8>>, Error> {
+        let mut token = HeaderValue::try_from(format!("Bearer {}", self.token.0.as_str()))
+            .map_err(|_| Error::InvalidToken)?;
+        token.set_sensitive(true);
+
+        let response = send(
+            self.http
+                .get(format!("{API}/{name}:access"))
+                .header(reqwest::header::AUTHORIZATION, token),
+        )
+        .await?;
+        parse_payload(response)
+    }
+
+    /// Fetch the string value of a secret version.
+    pub async fn secret(&self, name: &str) -> Result<Hide<String>, Error> {
+        let value = self.secret_bytes(name).await?;
+        String::from_utf8(value.0)
+            .map(Hide)
+            .map_err(|_| Error::NotString)
+    }
+
+    /// Fetch a secret version containing a JSON object, expanding its fields into labeled entries.
+    pub async fn secret_map(&self, name: &str) -> Result<SecretMap, Error> {
+        let secret = self.secret(name).await?;
+        expand_json(&secret).map_err(|_| Error::InvalidJson)
+    }
+}
+
+/// Get secret versions by name, using [`Client::secret`].
+impl super::AsyncSecretProvider for Client {
+    type Error = Error;
+
+    async fn get(&self, name: &str) -> Result<Hide<String>, Error> {
+        self.secret(name).await
+    }
+}
+
+async fn send(request: reqwest::RequestBuilder) -> Result<Value, Error> {
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        return Err(Error::Status(response.status().as_u16()));
+    }
+
+    let body = response.bytes().await?;
+    serde_json::from_slice(&body).map_err(|_| Error::InvalidResponse)
+}
+
+fn parse_payload(response: Value) -> Result<Hide<Vec<u8>>, Error> {
+    match response.pointer("/payload/data") {
+        Some(Value::String(data)) => {
+            codec::decode_base64(&Hide(data)).map_err(|_| Error::InvalidResponse)
+        }
+        _ => Err(Error::InvalidResponse),
+    }
+}
+
+fn parse_token(mut response: Value) -> Result<Hide<String>, Error> {
+    match response.get_mut("access_token").map(Value::take) {
+        Some(Value::String(token)) => Ok(Hide(token)),
+        _ => Err(Error::InvalidResponse),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_latest() {
+        assert_eq!(
+            latest("my-project", "db-password"),
+            "projects/my-project/secrets/db-password/versions/latest"
+        );
+    }
+
+    #[test]
+    fn test_parse_payload() {
+        let value = parse_payload(json!({
+            "name": "projects/123/secrets/db-password/versions/1",
+            "payload": { "data": "c2VjcmV0", "dataCrc32c": "2355516025" }
+        }))
+        .unwrap();
+        assert_eq!(value.0, b"secret");
+
+        assert!(matches!(
+            parse_payload(json!({ "payload": {} })),
+            Err(Error::InvalidResponse)
+        ));
+    }
+
+    #[test]
+    fn test_parse_token() {
+        let token = parse_token(json!({
+            "access_token": "ya29.token",
+            "expires_in": 3599,
+            "token_type": "Bearer"
+        }))
+        .unwrap();
+        assert_eq!(token.as_str(), "ya29.token");
+        assert!(format!("{:?}", Client::new(token)).contains("token: ***"));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::builder::wipe;
+use crate::{Hide, HideLock};
+use std::fmt::{Debug, Formatter};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// The token of the Kubernetes service account of the pod, reloaded from the mounted file.
+///
+/// Service account tokens are projected tokens, which expire and are rotated by the kubelet, so
+/// they must be read again periodically, instead of only once on startup. Like the Kubernetes
+/// clients do, the token is reloaded on access, if it's older than a minute (by default). If
+/// reloading fails, the previous token is kept.
+///
+/// ```rust,no_run
+/// use hide::providers::ServiceAccountToken;
+///
+/// let token = ServiceAccountToken::new()?;
+/// let header = format!("Bearer {}", token.get().as_str());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// Replaced tokens are wiped.
+pub struct ServiceAccountToken {
+    path: PathBuf,
+    interval: Duration,
+    token: HideLock<String>,
+    loaded: Mutex<Instant>,
+}
+
+impl ServiceAccountToken {
+    /// The path the token is mounted to.
+    pub const PATH: &'static str = "/var/run/secrets/kubernetes.io/serviceaccount/token";
+
+    /// Load the token from [`Self::PATH`].
+    pub fn new() -> io::Result<Self> {
+        Self::from_path(Self::PATH)
+    }
+
+    /// Load the token from a different path, e.g. of a projected volume with a custom audience.
+    pub fn from_path(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let token = read(&path)?;
+        Ok(Self {
+            path,
+            interval: Duration::from_secs(60),
+            token: HideLock::new(token.0),
+            loaded: Mutex::new(Instant::now()),
+        })
+    }
+
+    /// Reload the token on access, once it's older than the interval.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Get the current token, reloading it first, if required.
+    pub fn get(&self) -> Hide<String> {
+        let mut loaded = self.loaded.lock().unwrap_or_else(PoisonError::into_inner);
+        if loaded.elapsed() >= self.interval {
+            // retry on the next interval, if it fails
+            *loaded = Instant::now();
+            let _ = self.replace();
+        }
+        drop(loaded);
+
+        self.token.get()
+    }
+
+    /// Reload the token now.
+    pub fn reload(&self) -> io::Result<()> {
+        self.replace()?;
+        *self.loaded.lock().unwrap_or_else(PoisonError::into_inner) = Instant::now();
+        Ok(())
+    }
+
+    fn replace(&self) -> io::Result<()> {
+        let token = read(&self.path)?;
+        wipe(&mut self.token.replace(token.0).0);
+        Ok(())
+    }
+}
+
+/// Read the token, without the trailing newline.
+fn read(path: &Path) -> io::Result<Hide<String>> {
+    let mut token = Hide::<String>::from_file(path)?;
+    let len = token.0.trim_end().len();
+    token.0.truncate(len);
+    Ok(token)
+}
+
+impl Debug for ServiceAccountToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServiceAccountToken")
+            .field("path", &self.path)
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_reload() {
+        let path = std::env::temp_dir().join(format!("hide-sa-token-{}", std::process::id()));
+        std::fs::write(&path, "token1\n").unwrap();
+
+        let token = ServiceAccountToken::from_path(&path).unwrap();
+        std::fs::write(&path, "token2\n").unwrap();
+        assert_eq!(token.get().0, "token1");
+        token.reload().unwrap();
+        assert_eq!(token.get().0, "token2");
+
+        let token = token.interval(Duration::ZERO);
+        std::fs::write(&path, "token3").unwrap();
+        assert_eq!(token.get().0, "token3");
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(token.get().0, "token3");
+        assert!(token.reload().is_err());
+        assert!(ServiceAccountToken::from_path(&path).is_err());
+    }
+}
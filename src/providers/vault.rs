@@ -0,0 +1,367 @@
+//! Load secrets from HashiCorp Vault
+//!
+//! ```rust,no_run
+//! use hide::providers::vault::Client;
+//!
+//! # async fn example() -> Result<(), hide::providers::vault::Error> {
+//! // using `VAULT_ADDR` and `VAULT_TOKEN`
+//! let client = Client::from_env()?;
+//! let database = client.kv2("secret", "prod/db").await?;
+//! let password = database.get("password");
+//!
+//! // dynamic credentials, which must be renewed before the lease expires
+//! let mut lease = client.lease("database/creds/readonly").await?;
+//! let username = lease.secrets().get("username");
+//! // ... after `lease.renew_after()`
+//! client.renew(&mut lease, None).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::expand_object;
+use crate::{Hide, SecretMap};
+use reqwest::header::HeaderValue;
+use reqwest::{Method, RequestBuilder};
+use serde_json::{json, Value};
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// The location of the service account token, when running in a Kubernetes pod.
+pub const SERVICE_ACCOUNT_TOKEN: &str = "/var/run/secrets/kubernetes.io/serviceaccount/token";
+
+/// Failed to load a secret.
+#[derive(Debug)]
+pub enum Error {
+    /// A required environment variable is missing.
+    MissingEnv(&'static str),
+    /// Failed to read the service account token.
+    Io(std::io::Error),
+    /// Failed to perform the request.
+    Request(reqwest::Error),
+    /// Vault responded with an error status.
+    Status(u16),
+    /// The token can't be used as a header value.
+    InvalidToken,
+    /// The response doesn't have the expected structure.
+    InvalidResponse,
+    /// The lease can't be renewed.
+    NotRenewable,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingEnv(name) => write!(f, "missing environment variable: {name}"),
+            Self::Io(err) => write!(f, "failed to read service account token: {err}"),
+            Self::Request(err) => write!(f, "failed to perform request: {err}"),
+            Self::Status(status) => write!(f, "request failed with status: {status}"),
+            Self::InvalidToken => f.write_str("invalid token"),
+            Self::InvalidResponse => f.write_str("invalid response"),
+            Self::NotRenewable => f.write_str("lease is not renewable"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Request(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        Self::Request(err)
+    }
+}
+
+/// A client, authenticated with a Vault token.
+#[derive(Debug)]
+#[cfg_attr(not(feature = "paranoid"), derive(Clone))]
+pub struct Client {
+    http: reqwest::Client,
+    address: String,
+    token: Hide<String>,
+}
+
+impl Client {
+    /// Create a new client, using a token.
+    pub fn new(address: impl Into<String>, token: Hide<String>) -> Self {
+        let mut address = address.into();
+        address.truncate(address.trim_end_matches('/').len());
+
+        Self {
+            http: reqwest::Client::new(),
+            address,
+            token,
+        }
+    }
+
+    /// Create a new client, using the address of `VAULT_ADDR` and the token of `VAULT_TOKEN`.
+    pub fn from_env() -> Result<Self, Error> {
+        let address = std::env::var("VAULT_ADDR").map_err(|_| Error::MissingEnv("VAULT_ADDR"))?;
+        let token = std::env::var("VAULT_TOKEN").map_err(|_| Error::MissingEnv("VAULT_TOKEN"))?;
+        Ok(Self::new(address, Hide(token)))
+    }
+
+    /// Log in using the Kubernetes auth method, mounted at `kubernetes`, and the service account
+    /// token of the pod.
+    pub async fn kubernetes(address: impl Into<String>, role: &str) -> Result<Self, Error> {
+        let mut content = std::fs::read_to_string(SERVICE_ACCOUNT_TOKEN).map_err(Error::Io)?;
+        let jwt = Hide(content.trim().to_string());
+        crate::builder::wipe(&mut content);
+
+        Self::kubernetes_with(address, "kubernetes", role, &jwt).await
+    }
+
+    /// Log in using the Kubernetes auth method, mounted at `mount`, and a service account token.
+    pub async fn kubernetes_with(
+        address: impl Into<String>,
+        mount: &str,
+        role: &str,
+        jwt: &Hide<String>,
+    ) -> Result<Self, Error> {
+        let mut client = Self::new(address, Hide(String::new()));
+
+        let response = client
+            .send(
+                client
+                    .request(Method::POST, &format!("auth/{mount}/login"))
+                    .json(&json!({ "role": role, "jwt": jwt.0.as_str() })),
+            )
+            .await?;
+        client.token = parse_token(response)?;
+
+        Ok(client)
+    }
+
+    /// Read the latest version of a secret from a KV version 2 engine, mounted at `mount`.
+    pub async fn kv2(&self, mount: &str, path: &str) -> Result<SecretMap, Error> {
+        let response = self
+            .send(self.request(Method::GET, &format!("{mount}/data/{path}")))
+            .await?;
+        parse_kv2(response)
+    }
+
+    /// Read a secret which has a lease, like dynamic database credentials.
+    pub async fn lease(&self, path: &str) -> Result<Lease, Error> {
+        let response = self.send(self.request(Method::GET, path)).await?;
+        parse_lease(response)
+    }
+
+    /// Renew a lease, optionally requesting a new duration.
+    ///
+    /// Vault may choose a different duration, which will be stored in the lease.
+    pub async fn renew(&self, lease: &mut Lease, increment: Option<Duration>) -> Result<(), Error> {
+        if !lease.renewable {
+            return Err(Error::NotRenewable);
+        }
+
+        let mut body = json!({ "lease_id": lease.id });
+        if let Some(increment) = increment {
+            body["increment"] = increment.as_secs().into();
+        }
+
+        let response = self
+            .send(self.request(Method::PUT, "sys/leases/renew").json(&body))
+            .await?;
+        lease.duration = parse_duration(&response)?;
+
+        Ok(())
+    }
+
+    /// Renew the token of the client, returning its new duration.
+    pub async fn renew_token(&self, increment: Option<Duration>) -> Result<Duration, Error> {
+        let body = match increment {
+            Some(increment) => json!({ "increment": increment.as_secs() }),
+            None => json!({}),
+        };
+
+        let response = self
+            .send(
+                self.request(Method::POST, "auth/token/renew-self")
+                    .json(&body),
+            )
+            .await?;
+        response
+            .get("auth")
+            .ok_or(Error::InvalidResponse)
+            .and_then(parse_duration)
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.http
+            .request(method, format!("{}/v1/{path}", self.address))
+    }
+
+    async fn send(&self, request: RequestBuilder) -> Result<Value, Error> {
+        let request = match self.token.0.is_empty() {
+            true => request,
+            false => {
+                let mut token =
+                    HeaderValue::from_str(&self.token.0).map_err(|_| Error::InvalidToken)?;
+                token.set_sensitive(true);
+                request.header("X-Vault-Token", token)
+            }
+        };
+
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(Error::Status(response.status().as_u16()));
+        }
+
+        // the body is shared and immutable, take ownership to wipe it after parsing
+        let mut body = Vec::from(response.bytes().await?);
+        let result = serde_json::from_slice(&body).map_err(|_| Error::InvalidResponse);
+        crate::builder::wipe_bytes(&mut body);
+        result
+    }
+}
+
+/// A secret, which has a lease.
+#[derive(Debug)]
+pub struct Lease {
+    id: String,
+    duration: Duration,
+    renewable: bool,
+    secrets: SecretMap,
+}
+
+impl Lease {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The duration of the lease, starting from when it was read or last renewed.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    pub fn renewable(&self) -> bool {
+        self.renewable
+    }
+
+    /// The time after which the lease should be renewed, which is two thirds of its duration.
+    pub fn renew_after(&self) -> Duration {
+        self.duration * 2 / 3
+    }
+
+    pub fn secrets(&self) -> &SecretMap {
+        &self.secrets
+    }
+
+    pub fn into_secrets(self) -> SecretMap {
+        self.secrets
+    }
+}
+
+fn parse_object(value: Option<&mut Value>) -> Result<SecretMap, Error> {
+    match value.map(Value::take) {
+        Some(Value::Object(fields)) => Ok(expand_object(fields)),
+        _ => Err(Error::InvalidResponse),
+    }
+}
+
+fn parse_kv2(mut response: Value) -> Result<SecretMap, Error> {
+    parse_object(response.pointer_mut("/data/data"))
+}
+
+fn parse_lease(mut response: Value) -> Result<Lease, Error> {
+    Ok(Lease {
+        id: response
+            .get("lease_id")
+            .and_then(Value::as_str)
+            .ok_or(Error::InvalidResponse)?
+            .to_string(),
+        duration: parse_duration(&response)?,
+        renewable: response
+            .get("renewable")
+            .and_then(Value::as_bool)
+            .unwrap_or_default(),
+        secrets: parse_object(response.get_mut("data"))?,
+    })
+}
+
+fn parse_duration(response: &Value) -> Result<Duration, Error> {
+    response
+        .get("lease_duration")
+        .and_then(Value::as_u64)
+        .map(Duration::from_secs)
+        .ok_or(Error::InvalidResponse)
+}
+
+fn parse_token(mut response: Value) -> Result<Hide<String>, Error> {
+    match response.pointer_mut("/auth/client_token").map(Value::take) {
+        Some(Value::String(token)) => Ok(Hide(token)),
+        _ => Err(Error::InvalidResponse),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_address() {
+        let client = Client::new("https://vault:8200/", Hide("token".to_string()));
+        assert_eq!(client.address, "https://vault:8200");
+        assert!(format!("{client:?}").contains("token: ***"));
+    }
+
+    #[test]
+    fn test_parse_kv2() {
+        let secrets = parse_kv2(json!({
+            "data": {
+                "data": { "username": "admin", "password": "secret" },
+                "metadata": { "version": 3 }
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            secrets.labels().collect::<Vec<_>>(),
+            ["password", "username"]
+        );
+        assert_eq!(secrets.get("password").unwrap().as_str(), "secret");
+
+        assert!(matches!(
+            parse_kv2(json!({ "data": {} })),
+            Err(Error::InvalidResponse)
+        ));
+    }
+
+    #[test]
+    fn test_parse_lease() {
+        let lease = parse_lease(json!({
+            "lease_id": "database/creds/readonly/abc",
+            "lease_duration": 3600,
+            "renewable": true,
+            "data": { "username": "v-readonly", "password": "hunter2" }
+        }))
+        .unwrap();
+
+        assert_eq!(lease.id(), "database/creds/readonly/abc");
+        assert_eq!(lease.duration(), Duration::from_secs(3600));
+        assert_eq!(lease.renew_after(), Duration::from_secs(2400));
+        assert!(lease.renewable());
+        assert_eq!(lease.secrets().get("password").unwrap().as_str(), "hunter2");
+        assert!(!format!("{lease:?}").contains("hunter2"));
+    }
+
+    #[test]
+    fn test_parse_token() {
+        let token = parse_token(json!({
+            "auth": { "client_token": "hvs.token", "lease_duration": 60 }
+        }))
+        .unwrap();
+        assert_eq!(token.as_str(), "hvs.token");
+
+        assert!(matches!(
+            parse_token(json!({ "auth": null })),
+            Err(Error::InvalidResponse)
+        ));
+    }
+}
@@ -0,0 +1,248 @@
+use crate::builder::{wipe, wipe_bytes};
+use crate::Hide;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// A buffer for reading a secret, which is wiped when growing, or when reading fails.
+struct Buffer {
+    data: Vec<u8>,
+    len: usize,
+    max_len: usize,
+}
+
+impl Buffer {
+    fn new(max_len: usize) -> Self {
+        Self {
+            data: Vec::new(),
+            len: 0,
+            max_len,
+        }
+    }
+
+    /// The space to read into.
+    ///
+    /// This allows reading one byte more than the maximum length, to detect exceeding it.
+    fn spare(&mut self) -> &mut [u8] {
+        if self.len == self.data.len() {
+            let capacity = (self.len * 2).max(64).min(self.max_len.saturating_add(1));
+            let mut grown = vec![0; capacity];
+            grown[..self.len].copy_from_slice(&self.data[..self.len]);
+            wipe_bytes(&mut self.data);
+            self.data = grown;
+        }
+        &mut self.data[self.len..]
+    }
+
+    fn advance(&mut self, n: usize) -> io::Result<()> {
+        self.len += n;
+        match self.len > self.max_len {
+            true => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "secret exceeds the maximum length",
+            )),
+            false => Ok(()),
+        }
+    }
+
+    fn finish(mut self) -> Hide<Vec<u8>> {
+        let mut data = std::mem::take(&mut self.data);
+        data.truncate(self.len);
+        Hide(data)
+    }
+}
+
+impl Drop for Buffer {
+    fn drop(&mut self) {
+        wipe_bytes(&mut self.data);
+    }
+}
+
+impl Hide<Vec<u8>> {
+    /// Read a secret until the end of the input, e.g. from a pipe or socket.
+    ///
+    /// Reading more than `max_len` bytes fails with an error of the kind
+    /// [`io::ErrorKind::InvalidData`]. The data is read directly into the buffer of the result. When
+    /// it needs to grow, or reading fails, the old buffer is wiped. Wiping requires the `zeroize`
+    /// feature.
+    ///
+    /// ```rust
+    /// use hide::Hide;
+    ///
+    /// let key = Hide::<Vec<u8>>::read_from(&b"secret"[..], 32).unwrap();
+    /// assert_eq!(key.as_slice(), b"secret");
+    ///
+    /// assert!(Hide::<Vec<u8>>::read_from(&b"secret"[..], 4).is_err());
+    /// ```
+    ///
+    /// Unused capacity of the result only contains zeros.
+    pub fn read_from(mut reader: impl Read, max_len: usize) -> io::Result<Self> {
+        let mut buffer = Buffer::new(max_len);
+        loop {
+            match reader.read(buffer.spare()) {
+                Ok(0) => return Ok(buffer.finish()),
+                Ok(n) => buffer.advance(n)?,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Read a secret until the end of the input, like [`Self::read_from`], but asynchronously.
+    #[cfg(feature = "tokio")]
+    pub async fn read_from_async(
+        mut reader: impl tokio::io::AsyncRead + Unpin,
+        max_len: usize,
+    ) -> io::Result<Self> {
+        use tokio::io::AsyncReadExt;
+
+        let mut buffer = Buffer::new(max_len);
+        loop {
+            match reader.read(buffer.spare()).await {
+                Ok(0) => return Ok(buffer.finish()),
+                Ok(n) => buffer.advance(n)?,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+impl Hide<String> {
+    /// Read a secret from a file, like a secret mounted into a container.
+    ///
+    /// The content is used as it is, including any trailing newline. Unlike
+    /// [`std::fs::read_to_string`], intermediate buffers are wiped, see [`Hide::read_from`].
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let value = Hide::<Vec<u8>>::read_from(File::open(path)?, usize::MAX)?;
+        into_string(value)
+    }
+
+    /// Read a secret from the standard input, like `docker login --password-stdin` does.
+    ///
+    /// A single trailing newline is removed, so that both piping a file and typing the secret
+    /// work. Secrets longer than `max_len` bytes fail with an error of the kind
+    /// [`io::ErrorKind::InvalidData`]. Intermediate buffers are wiped, see [`Hide::read_from`].
+    ///
+    /// ```rust,no_run
+    /// use hide::Hide;
+    ///
+    /// // echo "$TOKEN" | program --token-stdin
+    /// let token = Hide::<String>::from_stdin(4096).unwrap();
+    /// ```
+    pub fn from_stdin(max_len: usize) -> io::Result<Self> {
+        read_trimmed(io::stdin().lock(), max_len)
+    }
+
+    /// Read a secret from a file, like [`Self::from_file`], but without blocking the runtime.
+    #[cfg(feature = "tokio")]
+    pub async fn from_file_async(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = tokio::fs::File::open(path).await?;
+        let value = Hide::<Vec<u8>>::read_from_async(file, usize::MAX).await?;
+        into_string(value)
+    }
+}
+
+/// Read a secret until the end of the input, removing a single trailing `\n` or `\r\n`.
+pub(crate) fn read_trimmed(reader: impl Read, max_len: usize) -> io::Result<Hide<String>> {
+    // allow for the newline, which doesn't count towards the maximum length
+    let mut value = into_string(Hide::<Vec<u8>>::read_from(
+        reader,
+        max_len.saturating_add(2),
+    )?)?;
+
+    if value.0.ends_with('\n') {
+        value.0.pop();
+        if value.0.ends_with('\r') {
+            value.0.pop();
+        }
+    }
+
+    if value.0.len() > max_len {
+        wipe(&mut value.0);
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "secret exceeds the maximum length",
+        ));
+    }
+
+    Ok(value)
+}
+
+fn into_string(value: Hide<Vec<u8>>) -> io::Result<Hide<String>> {
+    String::from_utf8(value.0).map(Hide).map_err(|err| {
+        wipe_bytes(&mut err.into_bytes());
+        io::Error::new(io::ErrorKind::InvalidData, "secret is not valid UTF-8")
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_read_from() {
+        let data = (0..200u8).collect::<Vec<_>>();
+
+        let value = Hide::<Vec<u8>>::read_from(data.as_slice(), 200).unwrap();
+        assert_eq!(value.0, data);
+
+        let value = Hide::<Vec<u8>>::read_from(io::empty(), 0).unwrap();
+        assert!(value.is_empty());
+
+        let err = Hide::<Vec<u8>>::read_from(data.as_slice(), 199).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_read_from_async() {
+        let data = (0..200u8).collect::<Vec<_>>();
+
+        let value = Hide::<Vec<u8>>::read_from_async(data.as_slice(), 1024)
+            .await
+            .unwrap();
+        assert_eq!(value.0, data);
+
+        let err = Hide::<Vec<u8>>::read_from_async(data.as_slice(), 100)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_from_file() {
+        let path = std::env::temp_dir().join(format!("hide-from-file-{}", std::process::id()));
+
+        std::fs::write(&path, "secret\n").unwrap();
+        assert_eq!(Hide::<String>::from_file(&path).unwrap().0, "secret\n");
+
+        std::fs::write(&path, [0xff, 0xfe]).unwrap();
+        let err = Hide::<String>::from_file(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(Hide::<String>::from_file(&path).is_err());
+    }
+
+    #[test]
+    fn test_read_trimmed() {
+        let value = read_trimmed(&b"secret\n"[..], 6).unwrap();
+        assert_eq!(value.0, "secret");
+
+        let value = read_trimmed(&b"secret\r\n"[..], 6).unwrap();
+        assert_eq!(value.0, "secret");
+
+        let value = read_trimmed(&b"secret\n\n"[..], 7).unwrap();
+        assert_eq!(value.0, "secret\n");
+
+        let value = read_trimmed(&b" secret "[..], 8).unwrap();
+        assert_eq!(value.0, " secret ");
+
+        let err = read_trimmed(&b"secret\n"[..], 5).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let err = read_trimmed(&b"secret\n\n"[..], 6).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,223 @@
+//! Integration with `reqwest-middleware`
+//!
+//! [`LoggingMiddleware`] logs requests and responses using `log`, scrubbing secrets before they
+//! reach the logger. So that debug logging of HTTP requests can stay enabled in production:
+//!
+//! ```rust
+//! use hide::reqwest_middleware::LoggingMiddleware;
+//! use reqwest_middleware::ClientBuilder;
+//!
+//! let client = ClientBuilder::new(reqwest::Client::new())
+//!     .with(LoggingMiddleware::default().bodies(true))
+//!     .build();
+//! ```
+
+use crate::scrub::{self, Scrubber};
+use crate::{url, SUBSTITUTE};
+use ::reqwest_middleware::{Middleware, Next, Result};
+use http::header::{self, HeaderMap, HeaderName};
+use http::Extensions;
+use log::Level;
+use reqwest::{Request, Response, ResponseBuilderExt};
+use std::fmt::Write;
+use std::sync::Arc;
+
+const TARGET: &str = "hide::reqwest";
+
+/// Log requests and responses at the debug level.
+///
+/// The log target is `hide::reqwest`. Nothing is logged, unless this level is enabled for it.
+///
+/// Values of headers marked as sensitive, or which are in the list of sensitive headers, are
+/// replaced with `***`. By default, this list contains `authorization`, `proxy-authorization`,
+/// `cookie`, `set-cookie`, and `x-api-key`. The URL is redacted using [`url::redact`]. All other
+/// values, as well as the URL and bodies, are scrubbed of registered secrets (see
+/// [`scrub::register`]), or of the secrets of a custom [`Scrubber`].
+///
+/// Bodies are only logged if enabled. Logging the body of a response requires reading it
+/// completely before passing it on.
+#[derive(Clone, Debug)]
+pub struct LoggingMiddleware {
+    headers: Arc<[HeaderName]>,
+    scrubber: Option<Scrubber>,
+    bodies: bool,
+}
+
+impl Default for LoggingMiddleware {
+    fn default() -> Self {
+        Self {
+            headers: Arc::new([
+                header::AUTHORIZATION,
+                header::PROXY_AUTHORIZATION,
+                header::COOKIE,
+                header::SET_COOKIE,
+                HeaderName::from_static("x-api-key"),
+            ]),
+            scrubber: None,
+            bodies: false,
+        }
+    }
+}
+
+impl LoggingMiddleware {
+    /// Add a header to the list of sensitive headers.
+    pub fn header(mut self, header: HeaderName) -> Self {
+        self.headers = self.headers.iter().cloned().chain([header]).collect();
+        self
+    }
+
+    /// Use a scrubber, instead of the registered secrets.
+    pub fn scrubber(mut self, scrubber: Scrubber) -> Self {
+        self.scrubber = Some(scrubber);
+        self
+    }
+
+    /// Log the bodies of requests and responses.
+    pub fn bodies(mut self, bodies: bool) -> Self {
+        self.bodies = bodies;
+        self
+    }
+
+    fn format_headers(&self, scrubber: &Scrubber, output: &mut String, headers: &HeaderMap) {
+        for (name, value) in headers {
+            let _ = match value.is_sensitive() || self.headers.contains(name) {
+                true => write!(output, "\n{name}: {SUBSTITUTE}"),
+                false => write!(
+                    output,
+                    "\n{name}: {}",
+                    String::from_utf8_lossy(&scrubber.scrub_bytes(value.as_bytes()))
+                ),
+            };
+        }
+    }
+
+    fn format_request(&self, scrubber: &Scrubber, request: &Request) -> String {
+        let mut output = format!(
+            "request: {} {}",
+            request.method(),
+            scrubber.scrub(&url::redact(request.url().as_str()).to_string())
+        );
+        self.format_headers(scrubber, &mut output, request.headers());
+
+        if self.bodies {
+            if let Some(body) = request.body().and_then(|body| body.as_bytes()) {
+                format_body(scrubber, &mut output, body);
+            }
+        }
+
+        output
+    }
+
+    fn format_response(&self, scrubber: &Scrubber, response: &http::response::Parts) -> String {
+        let mut output = format!("response: {}", response.status);
+        self.format_headers(scrubber, &mut output, &response.headers);
+        output
+    }
+}
+
+fn format_body(scrubber: &Scrubber, output: &mut String, body: &[u8]) {
+    output.push_str("\n\n");
+    output.push_str(&String::from_utf8_lossy(&scrubber.scrub_bytes(body)));
+}
+
+#[async_trait::async_trait]
+impl Middleware for LoggingMiddleware {
+    async fn handle(
+        &self,
+        request: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> Result<Response> {
+        if !log::log_enabled!(target: TARGET, Level::Debug) {
+            return next.run(request, extensions).await;
+        }
+
+        let scrubber = self.scrubber.clone().unwrap_or_else(scrub::registered);
+        log::debug!(target: TARGET, "{}", self.format_request(&scrubber, &request));
+
+        let response = next.run(request, extensions).await?;
+        let url = response.url().clone();
+        let response: http::Response<reqwest::Body> = response.into();
+        let (mut parts, body) = response.into_parts();
+        let mut output = self.format_response(&scrubber, &parts);
+
+        let body = match self.bodies {
+            true => {
+                let body = http::Response::new(body);
+                let body = Response::from(body).bytes().await?;
+                format_body(&scrubber, &mut output, &body);
+                reqwest::Body::from(body)
+            }
+            false => body,
+        };
+
+        log::debug!(target: TARGET, "{output}");
+
+        // converting the response dropped its URL
+        let (with_url, ()) = http::Response::builder()
+            .url(url)
+            .body(())
+            .map_err(|err| ::reqwest_middleware::Error::Middleware(err.into()))?
+            .into_parts();
+        parts.extensions.extend(with_url.extensions);
+
+        Ok(http::Response::from_parts(parts, body).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Hide;
+    use http::HeaderValue;
+    use reqwest::Method;
+
+    #[test]
+    fn test_format_request() {
+        let scrubber = Scrubber::new().with(&Hide("secret"));
+        let middleware = LoggingMiddleware::default()
+            .header(HeaderName::from_static("x-token"))
+            .bodies(true);
+
+        let mut request = Request::new(
+            Method::POST,
+            "https://example.com/login?key=secret".parse().unwrap(),
+        );
+        let headers = request.headers_mut();
+        headers.insert("authorization", HeaderValue::from_static("Bearer token"));
+        headers.insert("x-token", HeaderValue::from_static("token"));
+        headers.insert("x-request", HeaderValue::from_static("secret-id"));
+        let mut cookie = HeaderValue::from_static("token");
+        cookie.set_sensitive(true);
+        headers.insert("x-session", cookie);
+        *request.body_mut() = Some(r#"{"password":"secret"}"#.into());
+
+        assert_eq!(
+            middleware.format_request(&scrubber, &request),
+            r#"request: POST https://example.com/login?key=***
+authorization: ***
+x-token: ***
+x-request: ***-id
+x-session: ***
+
+{"password":"***"}"#
+        );
+    }
+
+    #[test]
+    fn test_format_response() {
+        let scrubber = Scrubber::new().with(&Hide("secret"));
+        let (parts, _) = http::Response::builder()
+            .status(201)
+            .header("set-cookie", "session=token")
+            .header("location", "/users/secret")
+            .body(())
+            .unwrap()
+            .into_parts();
+
+        assert_eq!(
+            LoggingMiddleware::default().format_response(&scrubber, &parts),
+            "response: 201 Created\nset-cookie: ***\nlocation: /users/***"
+        );
+    }
+}
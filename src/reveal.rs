@@ -0,0 +1,132 @@
+//! Decide if hidden values should be revealed in the debug output.
+
+use crate::Hide;
+#[cfg(feature = "testing")]
+use std::fmt::Display;
+use std::fmt::{Debug, Formatter};
+
+/// Check if the debug output should show the actual value.
+///
+/// With the `reveal-debug` feature, this is the case for builds having `debug_assertions`
+/// enabled. With the `reveal-env` feature, this is the case if the environment variable
+/// `HIDE_REVEAL` is set to `1`, also only for builds having `debug_assertions` enabled.
+///
+/// Release builds always redact, unless inside a `testing::reveal_scope`. With the `paranoid`
+/// feature, values are never revealed.
+pub(crate) fn enabled() -> bool {
+    if cfg!(feature = "paranoid") {
+        return false;
+    }
+    scope_enabled() || cfg!(debug_assertions) && (cfg!(feature = "reveal-debug") || env_enabled())
+}
+
+/// A hidden value, showing the actual value in the [`Debug`] output if enabled, see
+/// [`Hide::revealed`].
+pub struct Revealed<'a, T>(&'a T);
+
+impl<T> Hide<T> {
+    /// Show the actual value in the [`Debug`] output, if enabled by the `reveal-debug` or
+    /// `reveal-env` feature. Inside a [`reveal_scope`](crate::testing::reveal_scope), the value is
+    /// shown in the [`Debug`] and [`Display`](std::fmt::Display) output.
+    ///
+    /// The [`Debug`] output of [`Hide`] itself never shows the value, so that it doesn't require
+    /// the inner type to implement [`Debug`]. Revealing is opted into by formatting this instead:
+    ///
+    /// ```rust
+    /// use hide::Hide;
+    ///
+    /// let password = Hide("secret");
+    /// assert_eq!(format!("{password:?}"), "***");
+    /// eprintln!("connecting with {:?}", password.revealed());
+    /// ```
+    pub fn revealed(&self) -> Revealed<'_, T> {
+        Revealed(&self.0)
+    }
+}
+
+impl<T: Debug> Debug for Revealed<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match enabled() {
+            true => self.0.fmt(f),
+            false => Hide::<T>::fmt_hidden(f),
+        }
+    }
+}
+
+#[cfg(feature = "testing")]
+impl<T: Display> Display for Revealed<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match !cfg!(feature = "paranoid") && scope_enabled() {
+            true => self.0.fmt(f),
+            false => crate::write_substitute(f),
+        }
+    }
+}
+
+#[cfg(feature = "testing")]
+fn scope_enabled() -> bool {
+    crate::testing::revealed()
+}
+
+#[cfg(not(feature = "testing"))]
+fn scope_enabled() -> bool {
+    false
+}
+
+#[cfg(feature = "reveal-env")]
+fn env_enabled() -> bool {
+    env::enabled()
+}
+
+#[cfg(not(feature = "reveal-env"))]
+fn env_enabled() -> bool {
+    false
+}
+
+#[cfg(feature = "reveal-env")]
+mod env {
+    use std::ffi::OsStr;
+    use std::sync::OnceLock;
+
+    const VARIABLE: &str = "HIDE_REVEAL";
+
+    /// Check the environment variable, only once.
+    pub(super) fn enabled() -> bool {
+        static ENABLED: OnceLock<bool> = OnceLock::new();
+        *ENABLED.get_or_init(|| is_enabled(std::env::var_os(VARIABLE).as_deref()))
+    }
+
+    pub(super) fn is_enabled(value: Option<&OsStr>) -> bool {
+        value == Some(OsStr::new("1"))
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_is_enabled() {
+            assert!(is_enabled(Some(OsStr::new("1"))));
+            assert!(!is_enabled(Some(OsStr::new("0"))));
+            assert!(!is_enabled(Some(OsStr::new("true"))));
+            assert!(!is_enabled(None));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "reveal-debug"))]
+mod test {
+    use crate::Hide;
+
+    #[test]
+    fn test_reveal_debug() {
+        let value = Hide("foo");
+        if cfg!(debug_assertions) {
+            assert_eq!(format!("{:?}", value.revealed()), r#""foo""#);
+        } else {
+            assert_eq!(format!("{:?}", value.revealed()), "***");
+        }
+        assert_eq!(format!("{value:?}"), "***");
+        assert_eq!(format!("{value}"), "***");
+    }
+}
@@ -0,0 +1,81 @@
+//! Integration with `rocket`
+//!
+//! Form fields and path parameters can be parsed into hidden values. The value of failed form
+//! fields is replaced by `***` in the errors, and parameter errors are hidden.
+//!
+//! Note that a `Contextual` form still records the raw values of all fields.
+
+use crate::{Hide, SUBSTITUTE};
+use ::rocket::form::{self, DataField, FromFormField, ValueField};
+use ::rocket::request::FromParam;
+
+fn redact(mut errors: form::Errors<'_>) -> form::Errors<'_> {
+    for error in errors.iter_mut() {
+        error.value = Some(SUBSTITUTE.into());
+    }
+    errors
+}
+
+#[::rocket::async_trait]
+impl<'v, T> FromFormField<'v> for Hide<T>
+where
+    T: FromFormField<'v>,
+{
+    fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+        T::from_value(field).map(Hide).map_err(redact)
+    }
+
+    async fn from_data(field: DataField<'v, '_>) -> form::Result<'v, Self> {
+        T::from_data(field).await.map(Hide).map_err(redact)
+    }
+
+    fn default() -> Option<Self> {
+        T::default().map(Hide)
+    }
+}
+
+impl<'a, T> FromParam<'a> for Hide<T>
+where
+    T: FromParam<'a>,
+{
+    type Error = Hide<T::Error>;
+
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        T::from_param(param).map(Hide).map_err(Hide)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::rocket::form::Form;
+
+    #[allow(dead_code)]
+    #[derive(Debug, ::rocket::FromForm)]
+    struct Login {
+        user: String,
+        password: Hide<String>,
+        pin: Hide<u16>,
+    }
+
+    #[test]
+    fn test_form() {
+        let login = Form::<Login>::parse("user=foo&password=bar&pin=1234").unwrap();
+        assert_eq!(login.password.as_str(), "bar");
+        assert_eq!(*login.pin, 1234);
+
+        let errors = Form::<Login>::parse("user=foo&password=bar&pin=98765").unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].value.as_deref(), Some("***"));
+        assert!(!errors.to_string().contains("98765"));
+    }
+
+    #[test]
+    fn test_param() {
+        assert_eq!(*Hide::<u16>::from_param("1234").unwrap(), 1234);
+        assert_eq!(
+            format!("{:?}", Hide::<u16>::from_param("98765").unwrap_err()),
+            "***"
+        );
+    }
+}
@@ -0,0 +1,202 @@
+//! An Aho-Corasick automaton, finding the leftmost-longest match of a set of patterns
+
+/// A state of the automaton.
+#[derive(Clone, Default)]
+struct State {
+    /// The transitions, sorted by byte.
+    next: Vec<(u8, u32)>,
+    /// The state of the longest proper suffix, which is also a state.
+    fail: u32,
+    /// The length of the longest pattern ending in this state, or zero.
+    matched: u32,
+}
+
+/// The result of searching for the next match.
+#[derive(Debug, Eq, PartialEq)]
+pub(super) enum Next {
+    /// A match, with its start and length.
+    Match(usize, usize),
+    /// No match, up to the position. Data after it may still be part of a match, depending on
+    /// data which is not available yet.
+    Until(usize),
+}
+
+#[derive(Clone, Default)]
+pub(super) struct Automaton {
+    /// The states, the first one being the root. Empty, if there are no patterns.
+    states: Vec<State>,
+    /// The length of the longest pattern.
+    longest: usize,
+}
+
+impl Automaton {
+    pub(super) const fn new() -> Self {
+        Self {
+            states: Vec::new(),
+            longest: 0,
+        }
+    }
+
+    pub(super) fn build<'a>(patterns: impl IntoIterator<Item = &'a [u8]>) -> Self {
+        let mut states = vec![State::default()];
+        let mut longest = 0;
+
+        for pattern in patterns {
+            let mut current = 0;
+            for &byte in pattern {
+                current = match states[current]
+                    .next
+                    .binary_search_by_key(&byte, |(b, _)| *b)
+                {
+                    Ok(index) => states[current].next[index].1 as usize,
+                    Err(index) => {
+                        states.push(State::default());
+                        let state = (states.len() - 1) as u32;
+                        states[current].next.insert(index, (byte, state));
+                        state as usize
+                    }
+                };
+            }
+            states[current].matched = pattern.len() as u32;
+            longest = longest.max(pattern.len());
+        }
+
+        // breadth first, so that the fail state of each state is known before its children
+        let mut queue = std::collections::VecDeque::from([0usize]);
+        while let Some(current) = queue.pop_front() {
+            for index in 0..states[current].next.len() {
+                let (byte, child) = states[current].next[index];
+                let child = child as usize;
+
+                let fail = match current {
+                    0 => 0,
+                    _ => {
+                        let mut fail = states[current].fail as usize;
+                        loop {
+                            if let Some(next) = transition(&states[fail], byte) {
+                                break next;
+                            }
+                            if fail == 0 {
+                                break 0;
+                            }
+                            fail = states[fail].fail as usize;
+                        }
+                    }
+                };
+
+                states[child].fail = fail as u32;
+                if states[child].matched == 0 {
+                    states[child].matched = states[fail].matched;
+                }
+                queue.push_back(child);
+            }
+        }
+
+        if longest == 0 {
+            return Self::new();
+        }
+
+        Self { states, longest }
+    }
+
+    /// Find the next match, starting at the position.
+    ///
+    /// Of all matches, the one starting first wins, and of those, the longest one. If `complete`
+    /// is false, more data may follow, and a match is only reported once no other match can win
+    /// over it.
+    pub(super) fn next(&self, data: &[u8], position: usize, complete: bool) -> Next {
+        if self.states.is_empty() {
+            return Next::Until(data.len());
+        }
+
+        let mut state = 0;
+        let mut best: Option<(usize, usize)> = None;
+
+        for (index, &byte) in data.iter().enumerate().skip(position) {
+            state = self.step(state, byte);
+            let len = self.states[state].matched as usize;
+            if len > 0 {
+                // the longest pattern ending here is the one starting first
+                let start = index + 1 - len;
+                if best.is_none_or(|(s, l)| start < s || (start == s && len > l)) {
+                    best = Some((start, len));
+                }
+            }
+
+            if let Some((start, len)) = best {
+                // no other match can start at or before this one anymore
+                if index + 1 >= start + self.longest {
+                    return Next::Match(start, len);
+                }
+            }
+        }
+
+        match (best, complete) {
+            (Some((start, len)), true) => Next::Match(start, len),
+            (Some((start, _)), false) => Next::Until(start),
+            (None, true) => Next::Until(data.len()),
+            (None, false) => Next::Until(data.len().saturating_sub(self.longest - 1).max(position)),
+        }
+    }
+
+    fn step(&self, mut state: usize, byte: u8) -> usize {
+        loop {
+            if let Some(next) = transition(&self.states[state], byte) {
+                return next;
+            }
+            if state == 0 {
+                return 0;
+            }
+            state = self.states[state].fail as usize;
+        }
+    }
+}
+
+fn transition(state: &State, byte: u8) -> Option<usize> {
+    state
+        .next
+        .binary_search_by_key(&byte, |(b, _)| *b)
+        .ok()
+        .map(|index| state.next[index].1 as usize)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn automaton(patterns: &[&str]) -> Automaton {
+        Automaton::build(patterns.iter().map(|p| p.as_bytes()))
+    }
+
+    #[test]
+    fn test_next() {
+        let automaton = automaton(&["b", "abc", "ab", "abcd", "cde"]);
+        assert_eq!(automaton.longest, 4);
+
+        assert_eq!(automaton.next(b"xabcx", 0, true), Next::Match(1, 3));
+        assert_eq!(automaton.next(b"xabcd", 0, true), Next::Match(1, 4));
+        assert_eq!(automaton.next(b"xxbcde", 0, true), Next::Match(2, 1));
+        assert_eq!(automaton.next(b"xxxcde", 0, true), Next::Match(3, 3));
+        assert_eq!(automaton.next(b"xxxxxxx", 2, true), Next::Until(7));
+
+        // waiting for more data, as `abcd` may still follow
+        assert_eq!(automaton.next(b"xxabc", 0, false), Next::Until(2));
+        assert_eq!(automaton.next(b"xxxxxxxab", 0, false), Next::Until(7));
+        assert_eq!(automaton.next(b"xxxxxxx", 0, false), Next::Until(4));
+        assert_eq!(automaton.next(b"xxabcx", 0, false), Next::Match(2, 3));
+    }
+
+    #[test]
+    fn test_fail() {
+        // matching `aab` requires falling back from `aa` to `a`
+        let automaton = automaton(&["aab", "ac"]);
+        assert_eq!(automaton.next(b"aaab", 0, true), Next::Match(1, 3));
+        assert_eq!(automaton.next(b"aaac", 0, true), Next::Match(2, 2));
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(Automaton::new().next(b"abc", 0, false), Next::Until(3));
+        assert_eq!(automaton(&[""]).next(b"abc", 0, false), Next::Until(3));
+    }
+}
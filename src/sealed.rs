@@ -0,0 +1,365 @@
+//! Encrypting hidden values when serializing them
+//!
+//! [`Hide`] passes through serialization, so secrets end up in plain text wherever the value is
+//! persisted, like in job queues, caches, or configuration snapshots. A [`Sealed`] value is
+//! encrypted using a [`SealingKey`] instead, and decrypted when it's deserialized. The key is
+//! passed to [`Sealed::serialize_with`] and [`Sealed::deserialize_with`]:
+//!
+//! ```rust
+//! use hide::{sealed::{Sealed, SealingKey}, Hide};
+//!
+//! let key = SealingKey::new(&Hide([0x42; 32]));
+//! let token = Sealed("secret".to_string());
+//!
+//! let json = token.serialize_with(&key, serde_json::value::Serializer).unwrap();
+//! assert!(!json.to_string().contains("secret"));
+//!
+//! let token = Sealed::<String>::deserialize_with(&key, json).unwrap();
+//! assert_eq!(token.as_str(), "secret");
+//! ```
+//!
+//! The [`Serialize`] and [`Deserialize`] implementations use the key installed for the process,
+//! which works with derived implementations:
+//!
+//! ```rust
+//! use hide::{sealed::{self, Sealed, SealingKey}, Hide};
+//!
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Job {
+//!     user: String,
+//!     token: Sealed<String>,
+//! }
+//!
+//! sealed::install(SealingKey::new(&Hide([0x42; 32])));
+//!
+//! let job = Job { user: "admin".into(), token: Sealed("secret".into()) };
+//! let json = serde_json::to_string(&job).unwrap();
+//! assert!(!json.contains("secret"));
+//!
+//! let job: Job = serde_json::from_str(&json).unwrap();
+//! assert_eq!(job.token.as_str(), "secret");
+//! ```
+//!
+//! Values are serialized as JSON, and encrypted using XChaCha20-Poly1305 with a random nonce.
+//! For human-readable formats, the result is encoded using base64, otherwise as bytes.
+
+use crate::builder::wipe_bytes;
+use crate::{codec, Hide};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use serde::de::{DeserializeOwned, Error as _, SeqAccess, Visitor};
+use serde::ser::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::{Debug, Display, Formatter};
+#[cfg(not(feature = "paranoid"))]
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, PoisonError, RwLock};
+
+static KEY: RwLock<Option<Arc<SealingKey>>> = RwLock::new(None);
+
+/// The version of the format, preceding the nonce and ciphertext.
+const VERSION: u8 = 1;
+const NONCE_LEN: usize = 24;
+
+/// Failed to seal or open a value.
+///
+/// This never contains any part of the value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SealError {
+    /// No key is installed.
+    NoKey,
+    /// Failed to get a random nonce.
+    Random,
+    /// The value failed to serialize, or to deserialize after decrypting it.
+    Value,
+    /// The sealed value is invalid, or was sealed with a different key.
+    Invalid,
+}
+
+impl Display for SealError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoKey => f.write_str("no sealing key installed"),
+            Self::Random => f.write_str("failed to generate nonce"),
+            Self::Value => f.write_str("invalid sealed value content"),
+            Self::Invalid => f.write_str("invalid sealed value"),
+        }
+    }
+}
+
+impl std::error::Error for SealError {}
+
+/// A key for sealing values.
+pub struct SealingKey {
+    cipher: XChaCha20Poly1305,
+}
+
+impl SealingKey {
+    pub fn new(key: &Hide<[u8; 32]>) -> Self {
+        Self {
+            cipher: XChaCha20Poly1305::new(&key.0.into()),
+        }
+    }
+
+    /// Serialize and encrypt the value.
+    pub fn seal<T: Serialize + ?Sized>(&self, value: &T) -> Result<Vec<u8>, SealError> {
+        let mut nonce = [0; NONCE_LEN];
+        getrandom::fill(&mut nonce).map_err(|_| SealError::Random)?;
+
+        let mut plaintext = serde_json::to_vec(value).map_err(|_| SealError::Value)?;
+        let ciphertext = self
+            .cipher
+            .encrypt(XNonce::from_slice(&nonce), plaintext.as_slice());
+        wipe_bytes(&mut plaintext);
+
+        let ciphertext = ciphertext.map_err(|_| SealError::Invalid)?;
+        Ok([&[VERSION][..], &nonce, &ciphertext].concat())
+    }
+
+    /// Decrypt and deserialize a value, which was sealed with this key.
+    pub fn open<T: DeserializeOwned>(&self, sealed: &[u8]) -> Result<T, SealError> {
+        let Some((&VERSION, sealed)) = sealed.split_first() else {
+            return Err(SealError::Invalid);
+        };
+        if sealed.len() < NONCE_LEN {
+            return Err(SealError::Invalid);
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+
+        let mut plaintext = self
+            .cipher
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map_err(|_| SealError::Invalid)?;
+        // the error of serde_json may contain parts of the value, so it must be dropped
+        let value = serde_json::from_slice(&plaintext).map_err(|_| SealError::Value);
+        wipe_bytes(&mut plaintext);
+
+        value
+    }
+}
+
+impl Debug for SealingKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SealingKey").finish_non_exhaustive()
+    }
+}
+
+/// Install the key for sealing values, replacing the previous one.
+pub fn install(key: SealingKey) {
+    *KEY.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(key));
+}
+
+/// Remove the key, so that sealing values fails.
+pub fn uninstall() {
+    *KEY.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+fn installed() -> Result<Arc<SealingKey>, SealError> {
+    KEY.read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+        .ok_or(SealError::NoKey)
+}
+
+/// A value which is encrypted when serialized, see [`sealed`](crate::sealed).
+///
+/// For [`Debug`] and [`Display`], the placeholder is used, the same way as for [`Hide`]. With the
+/// `paranoid` feature, this implements neither `Clone` nor `Deref`, the same way as [`Hide`].
+#[derive(Default, Eq, PartialEq)]
+#[cfg_attr(not(feature = "paranoid"), derive(Clone))]
+#[cfg_attr(not(feature = "keyed-hash"), derive(Hash))]
+#[cfg_attr(not(feature = "keyed-ord"), derive(Ord, PartialOrd))]
+pub struct Sealed<T>(pub T);
+
+impl<T> Sealed<T> {
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    pub fn take(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Hide<T> {
+    /// Convert into a value which is encrypted when serialized, see [`sealed`](crate::sealed).
+    pub fn sealed(self) -> Sealed<T> {
+        Sealed(self.0)
+    }
+}
+
+#[cfg(not(feature = "paranoid"))]
+impl<T> Deref for Sealed<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(not(feature = "paranoid"))]
+impl<T> DerefMut for Sealed<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Sealed<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> From<Sealed<T>> for Hide<T> {
+    fn from(value: Sealed<T>) -> Self {
+        Hide(value.0)
+    }
+}
+
+impl<T> Debug for Sealed<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        crate::write_substitute(f)
+    }
+}
+
+impl<T> Display for Sealed<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        crate::write_substitute(f)
+    }
+}
+
+impl<T: Serialize> Sealed<T> {
+    /// Serialize the value, sealed with the key, instead of the installed one.
+    pub fn serialize_with<S: Serializer>(
+        &self,
+        key: &SealingKey,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let sealed = key.seal(&self.0).map_err(S::Error::custom)?;
+
+        match serializer.is_human_readable() {
+            true => serializer.serialize_str(&codec::encode_base64(&Hide(sealed)).0),
+            false => serializer.serialize_bytes(&sealed),
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Sealed<T> {
+    /// Deserialize a value, which was sealed with the key, instead of the installed one.
+    pub fn deserialize_with<'de, D: Deserializer<'de>>(
+        key: &SealingKey,
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let sealed = match deserializer.is_human_readable() {
+            true => deserializer.deserialize_str(SealedVisitor)?,
+            false => deserializer.deserialize_bytes(SealedVisitor)?,
+        };
+
+        key.open(&sealed).map(Self).map_err(D::Error::custom)
+    }
+}
+
+impl<T: Serialize> Serialize for Sealed<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let key = installed().map_err(S::Error::custom)?;
+        self.serialize_with(&key, serializer)
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Sealed<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let key = installed().map_err(D::Error::custom)?;
+        Self::deserialize_with(&key, deserializer)
+    }
+}
+
+struct SealedVisitor;
+
+impl<'de> Visitor<'de> for SealedVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("a sealed value")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        codec::decode_base64(&Hide(value))
+            .map(Hide::take)
+            .map_err(|_| E::custom(SealError::Invalid))
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+        Ok(value.to_vec())
+    }
+
+    fn visit_byte_buf<E: serde::de::Error>(self, value: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(value)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut result = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(byte) = seq.next_element()? {
+            result.push(byte);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_seal() {
+        let key = SealingKey::new(&Hide([1; 32]));
+        let other = SealingKey::new(&Hide([2; 32]));
+
+        let sealed = key.seal("secret").unwrap();
+        assert!(!sealed.windows(6).any(|window| window == b"secret"));
+        assert_ne!(sealed, key.seal("secret").unwrap());
+
+        assert_eq!(key.open::<String>(&sealed).unwrap(), "secret");
+        assert_eq!(other.open::<String>(&sealed), Err(SealError::Invalid));
+        assert_eq!(key.open::<u32>(&sealed), Err(SealError::Value));
+        assert_eq!(key.open::<String>(&sealed[..10]), Err(SealError::Invalid));
+        assert_eq!(key.open::<String>(&[]), Err(SealError::Invalid));
+    }
+
+    #[test]
+    fn test_serde() {
+        let key = SealingKey::new(&Hide([1; 32]));
+
+        let value = Sealed(vec!["secret".to_string()]);
+        assert_eq!(format!("{value:?}"), "***");
+
+        let mut json = Vec::new();
+        value
+            .serialize_with(&key, &mut serde_json::Serializer::new(&mut json))
+            .unwrap();
+        assert!(!json.windows(6).any(|window| window == b"secret"));
+        let mut de = serde_json::Deserializer::from_slice(&json);
+        let value = Sealed::<Vec<String>>::deserialize_with(&key, &mut de).unwrap();
+        assert_eq!(value.0, ["secret"]);
+
+        let mut de = serde_json::Deserializer::from_str(r#""c2VjcmV0""#);
+        let err = Sealed::<String>::deserialize_with(&key, &mut de).unwrap_err();
+        assert_eq!(err.to_string(), "invalid sealed value");
+    }
+
+    /// The only test using the installed key.
+    #[test]
+    fn test_installed() {
+        uninstall();
+        let err = serde_json::to_string(&Sealed("secret")).unwrap_err();
+        assert_eq!(err.to_string(), "no sealing key installed");
+
+        install(SealingKey::new(&Hide([1; 32])));
+        let json = serde_json::to_string(&Sealed("secret")).unwrap();
+        assert!(!json.contains("secret"));
+        let value: Sealed<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(value.0, "secret");
+
+        uninstall();
+        assert!(serde_json::from_str::<Sealed<String>>(&json).is_err());
+    }
+}
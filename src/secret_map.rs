@@ -0,0 +1,305 @@
+use crate::Hide;
+use std::collections::btree_map::{self, BTreeMap};
+use std::fmt::{Debug, Display, Formatter};
+
+/// A map of hidden values, by label.
+///
+/// The labels are shown in the debug output, the values are not:
+///
+/// ```rust
+/// use hide::SecretMap;
+///
+/// let mut secrets: SecretMap = SecretMap::new();
+/// secrets.insert("db_password", "secret");
+///
+/// assert_eq!(format!("{secrets:?}"), r#"{"db_password": ***}"#);
+/// assert_eq!(secrets.get("db_password").unwrap().as_str(), "secret");
+/// ```
+#[derive(Hash, Eq, PartialEq)]
+#[cfg_attr(not(feature = "paranoid"), derive(Clone))]
+#[cfg_attr(not(feature = "keyed-ord"), derive(Ord, PartialOrd))]
+pub struct SecretMap<T = String> {
+    secrets: BTreeMap<String, Hide<T>>,
+}
+
+impl<T> Default for SecretMap<T> {
+    fn default() -> Self {
+        Self {
+            secrets: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T> SecretMap<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a value, returning the previous value of the label.
+    pub fn insert(
+        &mut self,
+        label: impl Into<String>,
+        value: impl Into<Hide<T>>,
+    ) -> Option<Hide<T>> {
+        self.secrets.insert(label.into(), value.into())
+    }
+
+    pub fn get(&self, label: &str) -> Option<&Hide<T>> {
+        self.secrets.get(label)
+    }
+
+    pub fn get_mut(&mut self, label: &str) -> Option<&mut Hide<T>> {
+        self.secrets.get_mut(label)
+    }
+
+    pub fn remove(&mut self, label: &str) -> Option<Hide<T>> {
+        self.secrets.remove(label)
+    }
+
+    pub fn contains(&self, label: &str) -> bool {
+        self.secrets.contains_key(label)
+    }
+
+    pub fn len(&self) -> usize {
+        self.secrets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.secrets.is_empty()
+    }
+
+    /// Iterate over all labels, in order.
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.secrets.keys().map(String::as_str)
+    }
+
+    /// Iterate over all labels and values, in order of the labels.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Hide<T>)> {
+        self.secrets
+            .iter()
+            .map(|(label, value)| (label.as_str(), value))
+    }
+}
+
+impl<T: AsRef<[u8]>> SecretMap<T> {
+    /// Compare two maps, reporting the labels which were added, removed, or changed, in order.
+    ///
+    /// The values are compared in constant time, and are never part of the result. So that code
+    /// reloading secrets can log them, and re-connect if needed:
+    ///
+    /// ```rust
+    /// use hide::{SecretChange, SecretMap};
+    ///
+    /// let old: SecretMap = [("db_password", "foo"), ("token", "bar")].into_iter().collect();
+    /// let new: SecretMap = [("db_password", "baz"), ("api_key", "qux")].into_iter().collect();
+    ///
+    /// let changes = SecretMap::diff(&old, &new);
+    /// assert_eq!(
+    ///     changes,
+    ///     [
+    ///         SecretChange::Added("api_key".into()),
+    ///         SecretChange::Changed("db_password".into()),
+    ///         SecretChange::Removed("token".into()),
+    ///     ]
+    /// );
+    /// assert_eq!(changes[1].to_string(), "db_password changed");
+    /// ```
+    pub fn diff(old: &Self, new: &Self) -> Vec<SecretChange> {
+        let mut result = Vec::new();
+
+        for (label, value) in &old.secrets {
+            match new.secrets.get(label) {
+                Some(other) if crate::ct::eq(value.0.as_ref(), other.0.as_ref()) => {}
+                Some(_) => result.push(SecretChange::Changed(label.clone())),
+                None => result.push(SecretChange::Removed(label.clone())),
+            }
+        }
+        for label in new.secrets.keys() {
+            if !old.secrets.contains_key(label) {
+                result.push(SecretChange::Added(label.clone()));
+            }
+        }
+
+        result.sort_by(|a, b| a.label().cmp(b.label()));
+        result
+    }
+}
+
+impl SecretMap<String> {
+    /// Load all environment variables having the prefix.
+    ///
+    /// The label is the name of the variable, with the prefix removed and converted to lowercase.
+    /// So that with a prefix of `APP_SECRET_`, the variable `APP_SECRET_DB_PASSWORD` is stored as
+    /// `db_password`. Variables which are not valid unicode are skipped.
+    pub fn from_env(prefix: &str) -> Self {
+        std::env::vars_os()
+            .filter_map(|(name, value)| {
+                let label = name.to_str()?.strip_prefix(prefix)?.to_lowercase();
+                Some((label, value.into_string().ok()?))
+            })
+            .collect()
+    }
+}
+
+/// A change of a label between two [`SecretMap`]s, see [`SecretMap::diff`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SecretChange {
+    /// The label only exists in the new map.
+    Added(String),
+    /// The label only exists in the old map.
+    Removed(String),
+    /// The label exists in both maps, with different values.
+    Changed(String),
+}
+
+impl SecretChange {
+    pub fn label(&self) -> &str {
+        match self {
+            Self::Added(label) | Self::Removed(label) | Self::Changed(label) => label,
+        }
+    }
+}
+
+impl Display for SecretChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Added(label) => write!(f, "{label} added"),
+            Self::Removed(label) => write!(f, "{label} removed"),
+            Self::Changed(label) => write!(f, "{label} changed"),
+        }
+    }
+}
+
+impl<T> Debug for SecretMap<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut map = f.debug_map();
+        for label in self.secrets.keys() {
+            map.entry(label, &format_args!("{}", crate::SUBSTITUTE));
+        }
+        map.finish()
+    }
+}
+
+#[cfg(feature = "policy")]
+impl<T: AsRef<[u8]>> SecretMap<T> {
+    /// Render the values using the process-wide [`policy`](crate::policy), based on their labels.
+    pub fn masked(&self) -> crate::policy::Masked<'_, Self> {
+        crate::policy::Masked(self)
+    }
+}
+
+#[cfg(feature = "policy")]
+impl<T: AsRef<[u8]>> Debug for crate::policy::Masked<'_, SecretMap<T>> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut map = f.debug_map();
+        for (label, value) in &self.0.secrets {
+            let value = crate::policy::apply(label, value.0.as_ref());
+            map.entry(label, &format_args!("{value}"));
+        }
+        map.finish()
+    }
+}
+
+impl<K, V, T> Extend<(K, V)> for SecretMap<T>
+where
+    K: Into<String>,
+    V: Into<Hide<T>>,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (label, value) in iter {
+            self.insert(label, value);
+        }
+    }
+}
+
+impl<K, V, T> FromIterator<(K, V)> for SecretMap<T>
+where
+    K: Into<String>,
+    V: Into<Hide<T>>,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut result = Self::new();
+        result.extend(iter);
+        result
+    }
+}
+
+impl<T> IntoIterator for SecretMap<T> {
+    type Item = (String, Hide<T>);
+    type IntoIter = btree_map::IntoIter<String, Hide<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.secrets.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_map() {
+        let mut secrets: SecretMap = [("token", "foo"), ("db_password", "bar")]
+            .into_iter()
+            .collect();
+
+        assert_eq!(secrets.len(), 2);
+        assert_eq!(
+            secrets.labels().collect::<Vec<_>>(),
+            ["db_password", "token"]
+        );
+        assert_eq!(
+            format!("{secrets:#?}"),
+            r#"{
+    "db_password": ***,
+    "token": ***,
+}"#
+        );
+
+        assert_eq!(
+            secrets.insert("token", "baz").map(Hide::take).as_deref(),
+            Some("foo")
+        );
+        assert_eq!(secrets.get("token").unwrap().as_str(), "baz");
+        assert!(secrets.remove("token").is_some());
+        assert!(!secrets.contains("token"));
+    }
+
+    #[test]
+    fn test_diff() {
+        let old: SecretMap<Vec<u8>> = [("a", vec![1]), ("b", vec![2]), ("c", vec![3])]
+            .into_iter()
+            .collect();
+        let new: SecretMap<Vec<u8>> = [("a", vec![1]), ("b", vec![2, 0]), ("d", vec![4])]
+            .into_iter()
+            .collect();
+
+        assert_eq!(
+            SecretMap::diff(&old, &new),
+            [
+                SecretChange::Changed("b".into()),
+                SecretChange::Removed("c".into()),
+                SecretChange::Added("d".into()),
+            ]
+        );
+        assert_eq!(
+            SecretMap::diff(&new, &old),
+            [
+                SecretChange::Changed("b".into()),
+                SecretChange::Added("c".into()),
+                SecretChange::Removed("d".into()),
+            ]
+        );
+        assert!(SecretMap::diff(&old, &old).is_empty());
+        assert_eq!(SecretChange::Removed("c".into()).to_string(), "c removed");
+    }
+
+    #[test]
+    fn test_from_env() {
+        std::env::set_var("HIDE_TEST_SECRET_MAP_DB_PASSWORD", "foo");
+
+        let secrets = SecretMap::from_env("HIDE_TEST_SECRET_MAP_");
+        assert_eq!(secrets.labels().collect::<Vec<_>>(), ["db_password"]);
+        assert_eq!(secrets.get("db_password").unwrap().as_str(), "foo");
+    }
+}
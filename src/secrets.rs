@@ -0,0 +1,277 @@
+//! Distinct types for common kinds of secrets
+//!
+//! All of them are thin wrappers around [`Hide`], but are not interchangeable. So that a function
+//! expecting a [`Password`] can't be called with an [`ApiKey`].
+//!
+//! With the `paranoid` feature, their values are wiped when they are dropped.
+
+use crate::Hide;
+use std::convert::Infallible;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Deref;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+macro_rules! serde_secret {
+    ($name:ident, $inner:ty) => {
+        impl serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                self.0.serialize(serializer)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                Ok(Self(Hide::<$inner>::deserialize(deserializer)?))
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+macro_rules! serde_secret {
+    ($name:ident, $inner:ty) => {};
+}
+
+macro_rules! string_secret {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Hash, Ord, PartialOrd, Eq, PartialEq)]
+        #[cfg_attr(not(feature = "paranoid"), derive(Clone))]
+        pub struct $name(Hide<String>);
+
+        impl $name {
+            pub fn new(value: impl Into<String>) -> Self {
+                Self(Hide(value.into()))
+            }
+
+            pub fn take(mut self) -> Hide<String> {
+                std::mem::take(&mut self.0)
+            }
+        }
+
+        #[cfg(feature = "paranoid")]
+        impl Drop for $name {
+            fn drop(&mut self) {
+                crate::builder::wipe(&mut self.0 .0);
+            }
+        }
+
+        impl Deref for $name {
+            type Target = Hide<String>;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl Debug for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                f.debug_tuple(stringify!($name)).field(&self.0).finish()
+            }
+        }
+
+        #[cfg(not(feature = "paranoid"))]
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                Display::fmt(&self.0, f)
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(value: String) -> Self {
+                Self(Hide(value))
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                Self(Hide(value.to_string()))
+            }
+        }
+
+        impl From<Hide<String>> for $name {
+            fn from(value: Hide<String>) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for Hide<String> {
+            fn from(value: $name) -> Self {
+                value.take()
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Infallible;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok(s.into())
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0 .0
+            }
+        }
+
+        serde_secret!($name, String);
+    };
+}
+
+string_secret!(
+    /// A password.
+    Password
+);
+
+string_secret!(
+    /// A key, used to access an API.
+    ApiKey
+);
+
+string_secret!(
+    /// A bearer token, as used by the HTTP `Authorization` header.
+    BearerToken
+);
+
+/// Raw key material of a symmetric cipher.
+///
+/// Parsing from a string expects the key in hex encoding.
+#[derive(Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(not(feature = "paranoid"), derive(Clone))]
+pub struct SymmetricKey(Hide<Vec<u8>>);
+
+impl SymmetricKey {
+    pub fn new(value: impl Into<Vec<u8>>) -> Self {
+        Self(Hide(value.into()))
+    }
+
+    pub fn take(mut self) -> Hide<Vec<u8>> {
+        std::mem::take(&mut self.0)
+    }
+}
+
+#[cfg(feature = "paranoid")]
+impl Drop for SymmetricKey {
+    fn drop(&mut self) {
+        crate::builder::wipe_bytes(&mut self.0 .0);
+    }
+}
+
+impl Deref for SymmetricKey {
+    type Target = Hide<Vec<u8>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Debug for SymmetricKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SymmetricKey").field(&self.0).finish()
+    }
+}
+
+impl From<Vec<u8>> for SymmetricKey {
+    fn from(value: Vec<u8>) -> Self {
+        Self(Hide(value))
+    }
+}
+
+impl From<&[u8]> for SymmetricKey {
+    fn from(value: &[u8]) -> Self {
+        Self(Hide(value.to_vec()))
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for SymmetricKey {
+    fn from(value: [u8; N]) -> Self {
+        Self(Hide(value.to_vec()))
+    }
+}
+
+impl From<Hide<Vec<u8>>> for SymmetricKey {
+    fn from(value: Hide<Vec<u8>>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<SymmetricKey> for Hide<Vec<u8>> {
+    fn from(value: SymmetricKey) -> Self {
+        value.take()
+    }
+}
+
+impl AsRef<[u8]> for SymmetricKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0 .0
+    }
+}
+
+serde_secret!(SymmetricKey, Vec<u8>);
+
+/// Failed to parse a [`SymmetricKey`].
+///
+/// This never contains any part of the input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseKeyError;
+
+impl Display for ParseKeyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("invalid hex encoded key")
+    }
+}
+
+impl std::error::Error for ParseKeyError {}
+
+impl FromStr for SymmetricKey {
+    type Err = ParseKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        crate::codec::decode_hex_str(s.trim())
+            .map(Self::from)
+            .map_err(|_| ParseKeyError)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_debug() {
+        assert_eq!(format!("{:?}", Password::from("foo")), "Password(***)");
+        assert_eq!(format!("{}", ApiKey::from("foo")), "***");
+        assert_eq!(
+            format!("{:?}", SymmetricKey::from([1u8, 2, 3])),
+            "SymmetricKey(***)"
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let token: BearerToken = "foo".parse().unwrap();
+        assert_eq!(token.as_ref(), "foo");
+
+        let key: SymmetricKey = "00ff10".parse().unwrap();
+        assert_eq!(key.as_ref(), &[0x00, 0xff, 0x10]);
+
+        assert_eq!("0".parse::<SymmetricKey>(), Err(ParseKeyError));
+        assert_eq!("zz".parse::<SymmetricKey>(), Err(ParseKeyError));
+    }
+
+    #[test]
+    fn test_serde() {
+        assert_eq!(
+            serde_json::to_value(Password::from("foo")).unwrap(),
+            serde_json::json!("foo")
+        );
+    }
+}
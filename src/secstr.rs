@@ -0,0 +1,44 @@
+//! Conversions from and to the types of `secstr`.
+//!
+//! The values are moved, where possible. Otherwise, they are copied, and the original is wiped
+//! by `secstr`. Converting a `Hide<String>` into a [`SecUtf8`] uses the conversion of `secstr`.
+
+use crate::Hide;
+use ::secstr::{SecStr, SecUtf8};
+
+/// Copies the content, as [`SecStr`] doesn't allow taking it.
+impl From<SecStr> for Hide<Vec<u8>> {
+    fn from(value: SecStr) -> Self {
+        Hide(value.unsecure().to_vec())
+    }
+}
+
+impl From<Hide<Vec<u8>>> for SecStr {
+    fn from(value: Hide<Vec<u8>>) -> Self {
+        SecStr::new(value.0)
+    }
+}
+
+impl From<SecUtf8> for Hide<String> {
+    fn from(value: SecUtf8) -> Self {
+        Hide(value.into_unsecure())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_convert() {
+        let value: SecStr = Hide(b"secret".to_vec()).into();
+        assert_eq!(value.unsecure(), b"secret");
+        let value: Hide<Vec<u8>> = value.into();
+        assert_eq!(value.0, b"secret");
+
+        let value: SecUtf8 = Hide("secret".to_string()).into();
+        assert_eq!(value.unsecure(), "secret");
+        let value: Hide<String> = value.into();
+        assert_eq!(value.0, "secret");
+    }
+}
@@ -0,0 +1,189 @@
+use crate::Hide;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// A hidden value, which can cheaply be cloned, by sharing its content.
+///
+/// This is intended for secrets shared across many tasks. The value is dropped when the last
+/// reference is dropped. When created using [`SharedHide::zeroizing`], the value will be
+/// zeroized at this point too.
+///
+/// ```rust
+/// use hide::SharedHide;
+///
+/// let token = SharedHide::new("secret".to_string());
+/// let other = token.clone();
+///
+/// assert_eq!(format!("{other:?}"), "***");
+/// assert!(SharedHide::ptr_eq(&token, &other));
+/// ```
+pub struct SharedHide<T>(Arc<Inner<T>>);
+
+struct Inner<T> {
+    value: Hide<T>,
+    wipe: fn(&mut T),
+}
+
+impl<T> Drop for Inner<T> {
+    fn drop(&mut self) {
+        (self.wipe)(&mut self.value.0);
+    }
+}
+
+impl<T> SharedHide<T> {
+    pub fn new(value: T) -> Self {
+        Self(Arc::new(Inner {
+            value: Hide(value),
+            wipe: |_| {},
+        }))
+    }
+
+    /// Create a new instance, zeroizing the value when the last reference is dropped.
+    #[cfg(feature = "zeroize")]
+    pub fn zeroizing(value: T) -> Self
+    where
+        T: zeroize::Zeroize,
+    {
+        Self(Arc::new(Inner {
+            value: Hide(value),
+            wipe: zeroize::Zeroize::zeroize,
+        }))
+    }
+
+    /// Get the hidden value.
+    pub fn value(&self) -> &Hide<T> {
+        &self.0.value
+    }
+
+    /// Check if both instances share the same value.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.0, &other.0)
+    }
+
+    /// The number of instances sharing this value.
+    pub fn strong_count(this: &Self) -> usize {
+        Arc::strong_count(&this.0)
+    }
+}
+
+impl<T> Clone for SharedHide<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Deref for SharedHide<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0.value.0
+    }
+}
+
+impl<T> AsRef<T> for SharedHide<T> {
+    fn as_ref(&self) -> &T {
+        &self.0.value.0
+    }
+}
+
+impl<T> From<T> for SharedHide<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> From<Hide<T>> for SharedHide<T> {
+    fn from(value: Hide<T>) -> Self {
+        Self::new(value.0)
+    }
+}
+
+impl<T> Display for SharedHide<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        crate::write_substitute(f)
+    }
+}
+
+impl<T> Debug for SharedHide<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        crate::write_substitute(f)
+    }
+}
+
+impl<T> PartialEq for SharedHide<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.0.value == other.0.value
+    }
+}
+
+impl<T> Eq for SharedHide<T> where T: Eq {}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for SharedHide<T>
+where
+    T: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.value.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for SharedHide<T>
+where
+    T: serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(Self::new(T::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_shared() {
+        let value = SharedHide::new("foo".to_string());
+        let other = value.clone();
+
+        assert_eq!(SharedHide::strong_count(&value), 2);
+        drop(other);
+        assert_eq!(SharedHide::strong_count(&value), 1);
+        assert_eq!(value.as_str(), "foo");
+    }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn test_zeroizing() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        static WIPED: AtomicBool = AtomicBool::new(false);
+
+        struct Key;
+
+        impl zeroize::Zeroize for Key {
+            fn zeroize(&mut self) {
+                WIPED.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let value = SharedHide::zeroizing(Key);
+        let other = value.clone();
+
+        drop(value);
+        assert!(!WIPED.load(Ordering::SeqCst));
+        drop(other);
+        assert!(WIPED.load(Ordering::SeqCst));
+    }
+}
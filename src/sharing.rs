@@ -0,0 +1,205 @@
+//! Shamir's secret sharing
+//!
+//! Split a hidden value into `n` shares, of which any `k` can recover the value. Each share is a
+//! hidden value too.
+//!
+//! ```rust
+//! use hide::Hide;
+//!
+//! let key = Hide::new(b"recovery key".to_vec());
+//! let shares = key.split(5, 3).unwrap();
+//! assert_eq!(format!("{:?}", shares[0]), "***");
+//!
+//! let recovered = Hide::combine(&shares[1..4]).unwrap();
+//! assert_eq!(recovered, key);
+//! ```
+//!
+//! Shares use the field GF(2^8). The first byte of a share is its index, followed by one byte per
+//! byte of the value.
+
+use crate::builder::wipe_bytes;
+use crate::Hide;
+use std::fmt::{Display, Formatter};
+
+/// Failed to split or combine shares.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SharingError {
+    /// The threshold must be at least 1, and not higher than the number of shares.
+    InvalidThreshold,
+    /// No shares were provided.
+    NoShares,
+    /// The shares are malformed, have different lengths, or contain duplicates.
+    InvalidShares,
+    /// Failed to get random data.
+    Random,
+}
+
+impl Display for SharingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidThreshold => f.write_str("invalid threshold"),
+            Self::NoShares => f.write_str("no shares"),
+            Self::InvalidShares => f.write_str("invalid shares"),
+            Self::Random => f.write_str("failed to get random data"),
+        }
+    }
+}
+
+impl std::error::Error for SharingError {}
+
+impl Hide<Vec<u8>> {
+    /// Split the value into `n` shares, of which `k` are required to recover the value.
+    pub fn split(&self, n: u8, k: u8) -> Result<Vec<Hide<Vec<u8>>>, SharingError> {
+        if k == 0 || k > n {
+            return Err(SharingError::InvalidThreshold);
+        }
+
+        // the random coefficients of all polynomials, one polynomial per byte
+        let degree = k as usize - 1;
+        let mut coefficients = vec![0u8; self.0.len() * degree];
+        getrandom::fill(&mut coefficients).map_err(|_| SharingError::Random)?;
+
+        let shares = (1..=n)
+            .map(|x| {
+                let mut share = Vec::with_capacity(self.0.len() + 1);
+                share.push(x);
+                for (i, secret) in self.0.iter().enumerate() {
+                    let coefficients = &coefficients[i * degree..(i + 1) * degree];
+                    // Horner's method, starting with the highest coefficient
+                    let y = coefficients.iter().rev().fold(0, |y, c| mul(y, x) ^ c);
+                    share.push(mul(y, x) ^ secret);
+                }
+                Hide(share)
+            })
+            .collect();
+
+        wipe_bytes(&mut coefficients);
+        Ok(shares)
+    }
+
+    /// Recover a value from its shares.
+    ///
+    /// If less shares than the threshold are provided, the result is garbage. This can't be
+    /// detected.
+    pub fn combine(shares: &[Hide<Vec<u8>>]) -> Result<Self, SharingError> {
+        let len = shares.first().ok_or(SharingError::NoShares)?.0.len();
+        if len == 0 || shares.iter().any(|share| share.0.len() != len) {
+            return Err(SharingError::InvalidShares);
+        }
+
+        let xs = shares.iter().map(|share| share.0[0]).collect::<Vec<_>>();
+        if xs
+            .iter()
+            .enumerate()
+            .any(|(i, x)| *x == 0 || xs[..i].contains(x))
+        {
+            return Err(SharingError::InvalidShares);
+        }
+
+        // the Lagrange basis polynomials, evaluated at zero
+        let basis = xs
+            .iter()
+            .map(|xi| {
+                xs.iter()
+                    .filter(|xj| *xj != xi)
+                    .fold(1, |l, xj| mul(l, mul(*xj, inv(xj ^ xi))))
+            })
+            .collect::<Vec<_>>();
+
+        let mut result = Vec::with_capacity(len - 1);
+        for i in 1..len {
+            result.push(
+                shares
+                    .iter()
+                    .zip(&basis)
+                    .fold(0, |secret, (share, l)| secret ^ mul(share.0[i], *l)),
+            );
+        }
+
+        Ok(Hide(result))
+    }
+}
+
+/// Multiply in GF(2^8), using the AES polynomial.
+///
+/// This doesn't use lookup tables or branches depending on the values.
+fn mul(mut a: u8, mut b: u8) -> u8 {
+    let mut result = 0;
+    for _ in 0..8 {
+        result ^= a & (b & 1).wrapping_neg();
+        let carry = (a >> 7).wrapping_neg();
+        a = (a << 1) ^ (0x1b & carry);
+        b >>= 1;
+    }
+    result
+}
+
+/// Invert in GF(2^8), using `a^254`.
+fn inv(a: u8) -> u8 {
+    let mut result = 1;
+    let mut base = a;
+    let mut exp = 254u8;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul(result, base);
+        }
+        base = mul(base, base);
+        exp >>= 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_field() {
+        assert_eq!(mul(0x53, 0xca), 0x01);
+        assert_eq!(inv(0x53), 0xca);
+        for a in 1..=255 {
+            assert_eq!(mul(a, inv(a)), 1);
+        }
+    }
+
+    #[test]
+    fn test_split_combine() {
+        let key = Hide(b"top secret".to_vec());
+        let shares = key.split(5, 3).unwrap();
+
+        assert_eq!(shares.len(), 5);
+        assert!(shares.iter().all(|share| share.len() == key.len() + 1));
+
+        for combination in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+            let shares = combination.map(|i| shares[i].clone());
+            assert_eq!(Hide::combine(&shares).unwrap(), key);
+        }
+        assert_eq!(Hide::combine(&shares).unwrap(), key);
+        assert_ne!(Hide::combine(&shares[..2]).unwrap(), key);
+    }
+
+    #[test]
+    fn test_threshold_one() {
+        let key = Hide(vec![1, 2, 3]);
+        let shares = key.split(2, 1).unwrap();
+        assert_eq!(shares[0].0[1..], [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let key = Hide(vec![1, 2, 3]);
+        assert_eq!(key.split(2, 3), Err(SharingError::InvalidThreshold));
+        assert_eq!(key.split(2, 0), Err(SharingError::InvalidThreshold));
+
+        let shares = key.split(3, 2).unwrap();
+        assert_eq!(Hide::combine(&[]), Err(SharingError::NoShares));
+        assert_eq!(
+            Hide::combine(&[shares[0].clone(), shares[0].clone()]),
+            Err(SharingError::InvalidShares)
+        );
+        assert_eq!(
+            Hide::combine(&[shares[0].clone(), Hide(vec![2, 1])]),
+            Err(SharingError::InvalidShares)
+        );
+    }
+}
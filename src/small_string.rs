@@ -0,0 +1,86 @@
+//! Conversions for small string types, like `compact_str` and `smartstring`
+
+use crate::Hide;
+
+macro_rules! small_string {
+    ($(#[$meta:meta])* [$($generics:tt)*] $ty:ty) => {
+        $(#[$meta])*
+        impl<$($generics)*> From<&str> for Hide<$ty> {
+            fn from(value: &str) -> Self {
+                Hide(value.into())
+            }
+        }
+
+        $(#[$meta])*
+        impl<$($generics)*> From<String> for Hide<$ty> {
+            fn from(value: String) -> Self {
+                Hide(value.into())
+            }
+        }
+
+        $(#[$meta])*
+        impl<$($generics)*> From<Hide<String>> for Hide<$ty> {
+            fn from(value: Hide<String>) -> Self {
+                Hide(value.0.into())
+            }
+        }
+
+        $(#[$meta])*
+        impl<$($generics)*> From<Hide<$ty>> for Hide<String> {
+            fn from(value: Hide<$ty>) -> Self {
+                Hide(value.0.into())
+            }
+        }
+
+        $(#[$meta])*
+        impl<$($generics)*> AsRef<str> for Hide<$ty> {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+    };
+}
+
+small_string!(#[cfg(feature = "compact_str")] [] compact_str::CompactString);
+small_string!(
+    #[cfg(feature = "smartstring")]
+    [M: smartstring::SmartStringMode]
+    smartstring::SmartString<M>
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "compact_str")]
+    fn test_compact_str() {
+        let token: Hide<compact_str::CompactString> = "secret".into();
+        assert_eq!(AsRef::<str>::as_ref(&token), "secret");
+        assert_eq!(format!("{token:?}"), "***");
+
+        let token: Hide<String> = token.into();
+        let token: Hide<compact_str::CompactString> = token.into();
+        assert_eq!(token.0, "secret");
+    }
+
+    #[test]
+    #[cfg(feature = "smartstring")]
+    fn test_smartstring() {
+        use smartstring::alias::String as SmartString;
+
+        let token: Hide<SmartString> = String::from("secret").into();
+        assert_eq!(AsRef::<str>::as_ref(&token), "secret");
+
+        let token: Hide<String> = token.into();
+        assert_eq!(token.as_str(), "secret");
+    }
+
+    #[test]
+    #[cfg(all(feature = "compact_str", feature = "serde"))]
+    fn test_serde() {
+        let token: Hide<compact_str::CompactString> = serde_json::from_str(r#""secret""#).unwrap();
+        assert_eq!(token.0, "secret");
+        assert_eq!(serde_json::to_string(&token).unwrap(), r#""secret""#);
+    }
+}
@@ -0,0 +1,614 @@
+//! Decrypting values of SOPS-encrypted configuration files
+//!
+//! [SOPS](https://github.com/getsops/sops) encrypts the values of a document, keeping its
+//! structure, like `password: ENC[AES256_GCM,data:…,iv:…,tag:…,type:str]`. [`Decrypt`] wraps a
+//! deserializer and decrypts these values, using the data key of the document:
+//!
+//! ```rust
+//! use hide::{sops::{Decrypt, SopsKey}, Hide};
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Database {
+//!     user: String,
+//!     password: Hide<String>,
+//! }
+//!
+//! #[derive(Deserialize)]
+//! struct Config {
+//!     db: Database,
+//! }
+//!
+//! let key = SopsKey::new(&Hide(std::array::from_fn(|i| i as u8)));
+//! let mut json = serde_json::Deserializer::from_str(r#"{"db": {
+//!     "user": "admin",
+//!     "password": "ENC[AES256_GCM,data:SllngxNd,iv:BwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwc=,tag:+KZu3P+o/ddzOaBZGlWmeA==,type:str]"
+//! }}"#);
+//!
+//! let config = Config::deserialize(Decrypt::new(&mut json, &key)).unwrap();
+//! assert_eq!(config.db.password.as_str(), "secret");
+//! ```
+//!
+//! The data key is stored in the `sops` section of the document, encrypted for each of the
+//! configured key management services, and must be decrypted using those. The `sops` section
+//! itself is passed on unchanged, and the message authentication code of the document isn't
+//! verified. Keys of maps must be strings, and the format must be self-describing, like JSON or
+//! YAML.
+
+use crate::{codec, Hide};
+use aes_gcm::aead::consts::U32;
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::aes::Aes256;
+use aes_gcm::{AesGcm, Nonce};
+use serde::de::value::StringDeserializer;
+use serde::de::{
+    DeserializeSeed, Deserializer, EnumAccess, Error, IntoDeserializer, MapAccess, SeqAccess,
+    Visitor,
+};
+use std::fmt::{Debug, Display, Formatter};
+use std::rc::Rc;
+
+/// SOPS uses a nonce of 32 bytes.
+type Cipher = AesGcm<Aes256, U32>;
+
+/// Failed to decrypt a value.
+///
+/// This never contains any part of the value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SopsError {
+    /// The value isn't in the format of SOPS, or uses a different cipher.
+    Format,
+    /// The value was encrypted with a different key, or at a different path.
+    Decrypt,
+    /// The decrypted value doesn't match its type.
+    Type,
+}
+
+impl Display for SopsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Format => f.write_str("invalid SOPS value"),
+            Self::Decrypt => f.write_str("failed to decrypt SOPS value"),
+            Self::Type => f.write_str("decrypted SOPS value doesn't match its type"),
+        }
+    }
+}
+
+impl std::error::Error for SopsError {}
+
+/// The data key of a SOPS document.
+pub struct SopsKey {
+    cipher: Cipher,
+}
+
+impl SopsKey {
+    pub fn new(key: &Hide<[u8; 32]>) -> Self {
+        Self {
+            cipher: Cipher::new(&key.0.into()),
+        }
+    }
+
+    /// Decrypt a value, located at the path.
+    ///
+    /// The path consists of the keys leading to the value, each followed by a colon, like
+    /// `db:password:`. Indexes of sequences aren't part of the path.
+    pub fn decrypt(&self, value: &str, path: &str) -> Result<Hide<String>, SopsError> {
+        self.decrypt_typed(value, path).map(|(value, _)| value)
+    }
+
+    /// Decrypt a value, returning it together with its type.
+    fn decrypt_typed<'v>(
+        &self,
+        value: &'v str,
+        path: &str,
+    ) -> Result<(Hide<String>, &'v str), SopsError> {
+        let fields = value
+            .strip_prefix("ENC[AES256_GCM,")
+            .and_then(|value| value.strip_suffix(']'))
+            .ok_or(SopsError::Format)?;
+
+        let (mut data, mut iv, mut tag, mut kind) = (None, None, None, None);
+        for field in fields.split(',') {
+            let (name, value) = field.split_once(':').ok_or(SopsError::Format)?;
+            let field = match name {
+                "data" => &mut data,
+                "iv" => &mut iv,
+                "tag" => &mut tag,
+                "type" => &mut kind,
+                _ => return Err(SopsError::Format),
+            };
+            *field = Some(value);
+        }
+        let (Some(data), Some(iv), Some(tag), Some(kind)) = (data, iv, tag, kind) else {
+            return Err(SopsError::Format);
+        };
+
+        let decode = |value: &str| {
+            codec::decode_base64(&Hide(value))
+                .map(Hide::take)
+                .map_err(|_| SopsError::Format)
+        };
+        let iv = decode(iv)?;
+        if iv.len() != 32 {
+            return Err(SopsError::Format);
+        }
+        let mut ciphertext = decode(data)?;
+        ciphertext.extend(decode(tag)?);
+
+        let plaintext = self
+            .cipher
+            .decrypt(
+                Nonce::from_slice(&iv),
+                Payload {
+                    msg: &ciphertext,
+                    aad: path.as_bytes(),
+                },
+            )
+            .map_err(|_| SopsError::Decrypt)?;
+
+        match String::from_utf8(plaintext) {
+            Ok(plaintext) => Ok((Hide(plaintext), kind)),
+            Err(err) => {
+                crate::builder::wipe_bytes(&mut err.into_bytes());
+                Err(SopsError::Type)
+            }
+        }
+    }
+}
+
+impl Debug for SopsKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SopsKey").finish_non_exhaustive()
+    }
+}
+
+/// A deserializer, decrypting SOPS-encrypted values, see the [module documentation](self).
+pub struct Decrypt<'a, D> {
+    inner: D,
+    key: &'a SopsKey,
+    /// The path of the value, `None` inside the `sops` section.
+    path: Option<Rc<str>>,
+}
+
+impl<'a, D> Decrypt<'a, D> {
+    pub fn new(inner: D, key: &'a SopsKey) -> Self {
+        Self {
+            inner,
+            key,
+            path: Some("".into()),
+        }
+    }
+
+    fn wrap<V>(self, visitor: V) -> (D, Visit<'a, V>) {
+        let visitor = Visit {
+            inner: visitor,
+            key: self.key,
+            path: self.path,
+        };
+        (self.inner, visitor)
+    }
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                let (inner, visitor) = self.wrap(visitor);
+                inner.$method(visitor)
+            }
+        )*
+    };
+}
+
+macro_rules! any_deserialize {
+    ($($method:ident),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                let (inner, visitor) = self.wrap(visitor);
+                inner.deserialize_any(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for Decrypt<'_, D> {
+    type Error = D::Error;
+
+    // encrypted numbers and booleans are strings, so the type of the value must be checked first
+    any_deserialize!(
+        deserialize_bool,
+        deserialize_i8,
+        deserialize_i16,
+        deserialize_i32,
+        deserialize_i64,
+        deserialize_i128,
+        deserialize_u8,
+        deserialize_u16,
+        deserialize_u32,
+        deserialize_u64,
+        deserialize_u128,
+        deserialize_f32,
+        deserialize_f64,
+    );
+
+    forward_deserialize!(
+        deserialize_any,
+        deserialize_char,
+        deserialize_str,
+        deserialize_string,
+        deserialize_bytes,
+        deserialize_byte_buf,
+        deserialize_option,
+        deserialize_unit,
+        deserialize_seq,
+        deserialize_map,
+        deserialize_identifier,
+        deserialize_ignored_any,
+    );
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (inner, visitor) = self.wrap(visitor);
+        inner.deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (inner, visitor) = self.wrap(visitor);
+        inner.deserialize_newtype_struct(name, visitor)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (inner, visitor) = self.wrap(visitor);
+        inner.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (inner, visitor) = self.wrap(visitor);
+        inner.deserialize_tuple_struct(name, len, visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (inner, visitor) = self.wrap(visitor);
+        inner.deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (inner, visitor) = self.wrap(visitor);
+        inner.deserialize_enum(name, variants, visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+struct Visit<'a, V> {
+    inner: V,
+    key: &'a SopsKey,
+    path: Option<Rc<str>>,
+}
+
+impl<'a, V> Visit<'a, V> {
+    fn deserializer<D>(&self, inner: D) -> Decrypt<'a, D> {
+        Decrypt {
+            inner,
+            key: self.key,
+            path: self.path.clone(),
+        }
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident: $ty:ty),* $(,)?) => {
+        $(
+            fn $method<E: Error>(self, value: $ty) -> Result<Self::Value, E> {
+                self.inner.$method(value)
+            }
+        )*
+    };
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for Visit<'_, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.inner.expecting(f)
+    }
+
+    forward_visit!(
+        visit_bool: bool,
+        visit_i8: i8,
+        visit_i16: i16,
+        visit_i32: i32,
+        visit_i64: i64,
+        visit_i128: i128,
+        visit_u8: u8,
+        visit_u16: u16,
+        visit_u32: u32,
+        visit_u64: u64,
+        visit_u128: u128,
+        visit_f32: f32,
+        visit_f64: f64,
+        visit_char: char,
+        visit_bytes: &[u8],
+        visit_borrowed_bytes: &'de [u8],
+        visit_byte_buf: Vec<u8>,
+    );
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+        let Some(path) = self.path.as_deref().filter(|_| value.starts_with("ENC[")) else {
+            return self.inner.visit_str(value);
+        };
+
+        let (value, kind) = self.key.decrypt_typed(value, path).map_err(E::custom)?;
+        match kind {
+            "str" => self.inner.visit_string(value.0),
+            "bytes" => self.inner.visit_byte_buf(value.0.into_bytes()),
+            "int" => self.inner.visit_i64(parse(&value.0)?),
+            "float" => self.inner.visit_f64(parse(&value.0)?),
+            "bool" => match value.0.as_str() {
+                "True" | "true" => self.inner.visit_bool(true),
+                "False" | "false" => self.inner.visit_bool(false),
+                _ => Err(E::custom(SopsError::Type)),
+            },
+            _ => Err(E::custom(SopsError::Format)),
+        }
+    }
+
+    fn visit_borrowed_str<E: Error>(self, value: &'de str) -> Result<Self::Value, E> {
+        match value.starts_with("ENC[") {
+            true => self.visit_str(value),
+            false => self.inner.visit_borrowed_str(value),
+        }
+    }
+
+    fn visit_string<E: Error>(self, value: String) -> Result<Self::Value, E> {
+        match value.starts_with("ENC[") {
+            true => self.visit_str(&value),
+            false => self.inner.visit_string(value),
+        }
+    }
+
+    fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+        self.inner.visit_none()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let deserializer = self.deserializer(deserializer);
+        self.inner.visit_some(deserializer)
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+        self.inner.visit_unit()
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        let deserializer = self.deserializer(deserializer);
+        self.inner.visit_newtype_struct(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        self.inner.visit_seq(Seq {
+            inner: seq,
+            key: self.key,
+            path: self.path,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        self.inner.visit_map(Map {
+            inner: map,
+            key: self.key,
+            path: self.path,
+            next: None,
+        })
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        self.inner.visit_enum(data)
+    }
+}
+
+/// Parse a decrypted number, without including it in the error.
+fn parse<T: std::str::FromStr, E: Error>(value: &str) -> Result<T, E> {
+    value.parse().map_err(|_| E::custom(SopsError::Type))
+}
+
+/// Deserialize a value, at the path.
+struct Seed<'a, S> {
+    inner: S,
+    key: &'a SopsKey,
+    path: Option<Rc<str>>,
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for Seed<'_, S> {
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.inner.deserialize(Decrypt {
+            inner: deserializer,
+            key: self.key,
+            path: self.path,
+        })
+    }
+}
+
+struct Seq<'a, A> {
+    inner: A,
+    key: &'a SopsKey,
+    path: Option<Rc<str>>,
+}
+
+impl<'de, A: SeqAccess<'de>> SeqAccess<'de> for Seq<'_, A> {
+    type Error = A::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        self.inner.next_element_seed(Seed {
+            inner: seed,
+            key: self.key,
+            path: self.path.clone(),
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+struct Map<'a, A> {
+    inner: A,
+    key: &'a SopsKey,
+    path: Option<Rc<str>>,
+    /// The path of the next value.
+    next: Option<Rc<str>>,
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for Map<'_, A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some(key) = self.inner.next_key::<String>()? else {
+            return Ok(None);
+        };
+
+        self.next = match self.path.as_deref() {
+            // the metadata, which is encrypted differently
+            Some("") if key == "sops" => None,
+            Some(path) => Some(format!("{path}{key}:").into()),
+            None => None,
+        };
+
+        let key: StringDeserializer<A::Error> = key.into_deserializer();
+        seed.deserialize(key).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.inner.next_value_seed(Seed {
+            inner: seed,
+            key: self.key,
+            path: self.next.take(),
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde::Deserialize;
+    use std::collections::BTreeMap;
+
+    const IV: &str = "iv:BwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwc=";
+
+    fn key() -> SopsKey {
+        SopsKey::new(&Hide(std::array::from_fn(|i| i as u8)))
+    }
+
+    #[test]
+    fn test_decrypt() {
+        let value =
+            format!("ENC[AES256_GCM,data:SllngxNd,{IV},tag:+KZu3P+o/ddzOaBZGlWmeA==,type:str]");
+
+        assert_eq!(key().decrypt(&value, "db:password:").unwrap().0, "secret");
+        assert_eq!(key().decrypt(&value, "password:"), Err(SopsError::Decrypt));
+        assert_eq!(
+            SopsKey::new(&Hide([0; 32])).decrypt(&value, "db:password:"),
+            Err(SopsError::Decrypt)
+        );
+        assert_eq!(key().decrypt("secret", ""), Err(SopsError::Format));
+        assert_eq!(
+            key().decrypt("ENC[AES256_GCM,data:SllngxNd]", ""),
+            Err(SopsError::Format)
+        );
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Database {
+        port: u16,
+        password: Hide<String>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Config {
+        db: Database,
+        tokens: Vec<Hide<String>>,
+        sops: BTreeMap<String, String>,
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let json = format!(
+            r#"{{
+                "db": {{
+                    "port": "ENC[AES256_GCM,data:DAg3ww==,{IV},tag:lebb/a8rafVLPUDmsG4etQ==,type:int]",
+                    "password": "ENC[AES256_GCM,data:SllngxNd,{IV},tag:+KZu3P+o/ddzOaBZGlWmeA==,type:str]"
+                }},
+                "tokens": ["ENC[AES256_GCM,data:Sg9ng0Vd,{IV},tag:StsRABu0caMnWXuPl5CTyw==,type:str]"],
+                "sops": {{ "mac": "ENC[AES256_GCM,data:AAAA,{IV},tag:AAAA,type:str]" }}
+            }}"#
+        );
+
+        let key = key();
+        let mut json = serde_json::Deserializer::from_str(&json);
+        let config = Config::deserialize(Decrypt::new(&mut json, &key)).unwrap();
+
+        assert_eq!(config.db.port, 5432);
+        assert_eq!(config.db.password.as_str(), "secret");
+        assert_eq!(config.tokens[0].as_str(), "s3cr3t");
+        assert!(config.sops["mac"].starts_with("ENC["));
+    }
+
+    #[test]
+    fn test_error() {
+        let json = format!(
+            r#"{{"password": "ENC[AES256_GCM,data:SllngxNd,{IV},tag:+KZu3P+o/ddzOaBZGlWmeA==,type:str]"}}"#
+        );
+
+        let key = key();
+        let mut json = serde_json::Deserializer::from_str(&json);
+        let err =
+            BTreeMap::<String, String>::deserialize(Decrypt::new(&mut json, &key)).unwrap_err();
+        assert!(err.to_string().starts_with("failed to decrypt SOPS value"));
+    }
+}
@@ -0,0 +1,219 @@
+//! Loading a section of secrets from a configuration file
+//!
+//! Services often keep their secrets in a section of their configuration, with each entry either
+//! being the value itself, or a reference to where the value is found. [`SecretSources`]
+//! deserializes such a section, using any `serde` format like TOML, YAML, or JSON, and
+//! [`SecretSources::resolve`] loads the values into a [`SecretMap`]:
+//!
+//! ```rust
+//! use hide::sources::SecretSources;
+//!
+//! #[derive(serde::Deserialize)]
+//! struct Config {
+//!     listen: String,
+//!     secrets: SecretSources,
+//! }
+//!
+//! # std::env::set_var("APP_DB_PASSWORD", "secret");
+//! let config: Config = serde_json::from_str(r#"{
+//!     "listen": "[::]:8080",
+//!     "secrets": {
+//!         "api_key": "literal",
+//!         "db_password": "${APP_DB_PASSWORD}"
+//!     }
+//! }"#)?;
+//!
+//! let secrets = config.secrets.resolve()?;
+//! assert_eq!(secrets.get("api_key").unwrap().0, "literal");
+//! assert_eq!(secrets.get("db_password").unwrap().0, "secret");
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! An entry is one of:
+//!
+//! * `${NAME}`: The value of the environment variable `NAME`.
+//! * `file:PATH`: The content of the file at `PATH`, with a trailing newline removed.
+//! * Anything else: The value itself.
+
+use crate::providers::{Env, SecretProvider};
+use crate::{Hide, SecretMap};
+use serde::de::{Deserialize, Deserializer, IgnoredAny, MapAccess, Visitor};
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Formatter};
+use std::fs::File;
+use std::io;
+
+/// The sources of secrets, by label.
+///
+/// Only the labels are shown in the debug output, as entries may contain the values themselves.
+#[derive(Default, Eq, PartialEq)]
+#[cfg_attr(not(feature = "paranoid"), derive(Clone))]
+pub struct SecretSources {
+    sources: BTreeMap<String, Hide<String>>,
+}
+
+impl SecretSources {
+    /// Deserialize the sources from a section of the top-level map, ignoring all other entries.
+    ///
+    /// This allows loading the secrets without knowing the structure of the rest of the
+    /// configuration. A missing section results in no sources.
+    ///
+    /// ```rust
+    /// use hide::sources::SecretSources;
+    ///
+    /// let input = r#"{"listen": "[::]:8080", "secrets": {"api_key": "literal"}}"#;
+    /// let sources = SecretSources::from_section(
+    ///     &mut serde_json::Deserializer::from_str(input),
+    ///     "secrets",
+    /// )?;
+    ///
+    /// assert_eq!(sources.labels().collect::<Vec<_>>(), ["api_key"]);
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn from_section<'de, D>(deserializer: D, section: &str) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(SectionVisitor { section })
+    }
+
+    /// Iterate over all labels, in order.
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.sources.keys().map(String::as_str)
+    }
+
+    /// Load the values of all entries.
+    ///
+    /// Fails if an environment variable is not set, or isn't valid unicode, or if a file can't be
+    /// read. The error contains the label of the entry, but not the entry itself.
+    pub fn resolve(&self) -> io::Result<SecretMap> {
+        let mut result = SecretMap::new();
+        for (label, source) in &self.sources {
+            let value = resolve(source.0.as_str()).map_err(|err| {
+                io::Error::new(err.kind(), format!("failed to load secret {label}: {err}"))
+            })?;
+            result.insert(label.as_str(), value);
+        }
+        Ok(result)
+    }
+}
+
+fn resolve(source: &str) -> io::Result<Hide<String>> {
+    if let Some(name) = source
+        .strip_prefix("${")
+        .and_then(|name| name.strip_suffix('}'))
+    {
+        return Env::new().get(name);
+    }
+
+    if let Some(path) = source.strip_prefix("file:") {
+        return crate::read::read_trimmed(File::open(path)?, usize::MAX);
+    }
+
+    Ok(Hide(source.to_string()))
+}
+
+impl Debug for SecretSources {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut map = f.debug_map();
+        for label in self.sources.keys() {
+            map.entry(label, &format_args!("{}", crate::SUBSTITUTE));
+        }
+        map.finish()
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretSources {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Self {
+            sources: BTreeMap::deserialize(deserializer)?,
+        })
+    }
+}
+
+struct SectionVisitor<'a> {
+    section: &'a str,
+}
+
+impl<'de> Visitor<'de> for SectionVisitor<'_> {
+    type Value = SecretSources;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.write_str("a map")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut result = SecretSources::default();
+        while let Some(key) = map.next_key::<String>()? {
+            if key == self.section {
+                result = map.next_value()?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sources(input: &str) -> SecretSources {
+        SecretSources::from_section(&mut serde_json::Deserializer::from_str(input), "secrets")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_resolve() {
+        let path = std::env::temp_dir().join(format!("hide-sources-{}", std::process::id()));
+        std::fs::write(&path, "from-file\n").unwrap();
+        std::env::set_var("HIDE_TEST_SOURCES", "from-env");
+
+        let input = format!(
+            r#"{{"other": [1, {{"secrets": 2}}], "secrets": {{
+                "env": "${{HIDE_TEST_SOURCES}}",
+                "file": "file:{}",
+                "literal": "literal"
+            }}}}"#,
+            path.display()
+        );
+        let sources = sources(&input);
+        assert_eq!(
+            format!("{sources:?}"),
+            r#"{"env": ***, "file": ***, "literal": ***}"#
+        );
+
+        let secrets = sources.resolve().unwrap();
+        assert_eq!(secrets.get("env").unwrap().0, "from-env");
+        assert_eq!(secrets.get("file").unwrap().0, "from-file");
+        assert_eq!(secrets.get("literal").unwrap().0, "literal");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = sources(r#"{"secrets": {"token": "${HIDE_TEST_SOURCES_UNSET}"}}"#)
+            .resolve()
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert_eq!(
+            err.to_string(),
+            "failed to load secret token: environment variable HIDE_TEST_SOURCES_UNSET is not set"
+        );
+
+        let err = sources(r#"{"secrets": {"token": "file:/nonexistent/hide"}}"#)
+            .resolve()
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+        assert_eq!(sources(r#"{"other": {}}"#), SecretSources::default());
+    }
+}
@@ -0,0 +1,89 @@
+use crate::Hide;
+use std::str::FromStr;
+
+/// String transformations, which keep the result hidden.
+///
+/// These shadow the methods of [`str`] with the same name, which would return an unprotected
+/// value.
+///
+/// ```rust
+/// use hide::Hide;
+///
+/// let token = Hide::new("Bearer abc123\n".to_string());
+/// let token = token.trim().strip_prefix("Bearer ").unwrap();
+/// assert_eq!(token.as_str(), "abc123");
+/// assert_eq!(format!("{token:?}"), "***");
+/// ```
+impl Hide<String> {
+    /// Transform the string, keeping the result hidden.
+    pub fn map_str<F>(&self, f: F) -> Hide<String>
+    where
+        F: FnOnce(&str) -> String,
+    {
+        Hide(f(&self.0))
+    }
+
+    /// Remove leading and trailing whitespace.
+    pub fn trim(&self) -> Hide<String> {
+        Hide(self.0.trim().to_string())
+    }
+
+    pub fn to_lowercase(&self) -> Hide<String> {
+        Hide(self.0.to_lowercase())
+    }
+
+    pub fn to_uppercase(&self) -> Hide<String> {
+        Hide(self.0.to_uppercase())
+    }
+
+    /// Remove a prefix, returning `None` if the string doesn't start with it.
+    pub fn strip_prefix(&self, prefix: &str) -> Option<Hide<String>> {
+        self.0.strip_prefix(prefix).map(|s| Hide(s.to_string()))
+    }
+
+    /// Remove a suffix, returning `None` if the string doesn't end with it.
+    pub fn strip_suffix(&self, suffix: &str) -> Option<Hide<String>> {
+        self.0.strip_suffix(suffix).map(|s| Hide(s.to_string()))
+    }
+
+    /// Parse into a different type, keeping the result hidden.
+    ///
+    /// ```rust
+    /// use hide::Hide;
+    ///
+    /// let pin = Hide::new("1234".to_string());
+    /// let pin: Hide<u16> = pin.parse_inner().unwrap();
+    /// assert_eq!(*pin, 1234);
+    /// ```
+    ///
+    /// The error is the one of the target type. Some types include the input in their error,
+    /// which should be checked before showing it.
+    pub fn parse_inner<U: FromStr>(&self) -> Result<Hide<U>, U::Err> {
+        self.0.parse().map(Hide)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_map_str() {
+        let value = Hide(" Foo\r\n".to_string());
+
+        assert_eq!(value.trim().0, "Foo");
+        assert_eq!(value.to_lowercase().0, " foo\r\n");
+        assert_eq!(value.to_uppercase().0, " FOO\r\n");
+        assert_eq!(value.map_str(|s| s.replace(['\r', '\n'], "")).0, " Foo");
+        assert_eq!(value.trim().strip_prefix("F").unwrap().0, "oo");
+        assert_eq!(value.trim().strip_suffix("o").unwrap().0, "Fo");
+        assert!(value.strip_prefix("Foo").is_none());
+    }
+
+    #[test]
+    fn test_parse_inner() {
+        let port = Hide("5432".to_string()).parse_inner::<u16>().unwrap();
+        assert_eq!(port.0, 5432);
+        assert!(Hide("abc".to_string()).parse_inner::<u16>().is_err());
+    }
+}
@@ -0,0 +1,136 @@
+//! Integration with `tonic`
+//!
+//! ```rust
+//! use hide::Hide;
+//! use hide::tonic::SensitiveMetadata;
+//! use tonic::metadata::MetadataMap;
+//!
+//! let mut metadata = MetadataMap::new();
+//! let token = Hide::new("Bearer secret".to_string());
+//! hide::tonic::insert(&mut metadata, "authorization", &token).unwrap();
+//! assert!(!format!("{metadata:?}").contains("secret"));
+//!
+//! // on the server side, used as an interceptor
+//! let mut metadata = MetadataMap::new();
+//! metadata.insert("x-api-key", "secret".parse().unwrap());
+//! SensitiveMetadata::default().apply(&mut metadata);
+//! assert!(!format!("{metadata:?}").contains("secret"));
+//! ```
+
+use crate::Hide;
+use ::tonic::metadata::errors::InvalidMetadataValue;
+use ::tonic::metadata::{MetadataKey, MetadataMap, MetadataValue};
+use ::tonic::service::Interceptor;
+use ::tonic::{Request, Status};
+
+/// Insert a hidden value into the metadata, marked as sensitive.
+///
+/// Sensitive values are shown as `Sensitive` in the debug output of the metadata.
+pub fn insert(
+    metadata: &mut MetadataMap,
+    key: &'static str,
+    value: &Hide<String>,
+) -> Result<(), InvalidMetadataValue> {
+    let mut value = MetadataValue::try_from(value.0.as_str())?;
+    value.set_sensitive(true);
+    metadata.insert(MetadataKey::from_static(key), value);
+    Ok(())
+}
+
+/// Mark metadata values of a configurable set of keys as sensitive.
+///
+/// Used as an interceptor, all incoming requests will have those values marked as sensitive
+/// before being passed on to the service. So that tracing or logging the request doesn't show
+/// them.
+///
+/// By default, this contains `authorization`, `cookie`, and `x-api-key`.
+#[derive(Clone, Debug)]
+pub struct SensitiveMetadata {
+    keys: Vec<String>,
+}
+
+impl Default for SensitiveMetadata {
+    fn default() -> Self {
+        Self::new()
+            .key("authorization")
+            .key("cookie")
+            .key("x-api-key")
+    }
+}
+
+impl SensitiveMetadata {
+    /// Create a new instance, without any keys.
+    pub fn new() -> Self {
+        Self { keys: Vec::new() }
+    }
+
+    /// Add a key, which is matched case-insensitively.
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.keys.push(key.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Mark all values of the configured keys as sensitive.
+    pub fn apply(&self, metadata: &mut MetadataMap) {
+        for key in &self.keys {
+            match key.ends_with("-bin") {
+                true => {
+                    if let Ok(::tonic::metadata::Entry::Occupied(mut entry)) =
+                        metadata.entry_bin(key.as_str())
+                    {
+                        entry.iter_mut().for_each(|value| value.set_sensitive(true));
+                    }
+                }
+                false => {
+                    if let Ok(::tonic::metadata::Entry::Occupied(mut entry)) =
+                        metadata.entry(key.as_str())
+                    {
+                        entry.iter_mut().for_each(|value| value.set_sensitive(true));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Interceptor for SensitiveMetadata {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        self.apply(request.metadata_mut());
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_insert() {
+        let mut metadata = MetadataMap::new();
+        insert(&mut metadata, "authorization", &Hide("secret".to_string())).unwrap();
+
+        let value = metadata.get("authorization").unwrap();
+        assert!(value.is_sensitive());
+        assert_eq!(value, "secret");
+        assert!(insert(&mut metadata, "authorization", &Hide("\n".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_interceptor() {
+        let mut request = Request::new(());
+        let metadata = request.metadata_mut();
+        metadata.insert("x-api-key", "foo".parse().unwrap());
+        metadata.append("x-api-key", "bar".parse().unwrap());
+        metadata.insert_bin("x-token-bin", MetadataValue::from_bytes(b"baz"));
+        metadata.insert("x-request-id", "42".parse().unwrap());
+
+        let mut interceptor = SensitiveMetadata::default().key("X-Token-Bin");
+        let request = interceptor.call(request).unwrap();
+
+        let debug = format!("{request:?}");
+        assert!(!debug.contains("foo"));
+        assert!(!debug.contains("bar"));
+        assert!(!debug.contains("YmF6"));
+        assert!(debug.contains("42"));
+    }
+}
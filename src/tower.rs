@@ -0,0 +1,171 @@
+//! Integration with `tower`
+//!
+//! [`SensitiveHeadersLayer`] marks headers of requests and responses as sensitive, so that they
+//! are shown as `Sensitive` in their debug output. When added outside of a tracing layer (like
+//! `TraceLayer` of `tower-http`), the headers won't show up in its output:
+//!
+//! ```rust
+//! use hide::tower::SensitiveHeadersLayer;
+//! use http::{Request, Response};
+//! use tower::{service_fn, Layer, ServiceExt};
+//!
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! let service = SensitiveHeadersLayer::default().layer(service_fn(|request: Request<()>| async move {
+//!     assert!(!format!("{request:?}").contains("secret"));
+//!     Ok::<_, std::convert::Infallible>(Response::new(()))
+//! }));
+//!
+//! let request = Request::builder()
+//!     .header("authorization", "Bearer secret")
+//!     .body(())
+//!     .unwrap();
+//! service.oneshot(request).await.unwrap();
+//! # });
+//! ```
+
+#[cfg(feature = "tower-http")]
+pub mod trace;
+
+use http::header::{self, Entry, HeaderMap, HeaderName};
+use http::{Request, Response};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Mark a set of request and response headers as sensitive.
+///
+/// By default, this contains `authorization`, `proxy-authorization`, `cookie`, `set-cookie`, and
+/// `x-api-key`.
+#[derive(Clone, Debug)]
+pub struct SensitiveHeadersLayer {
+    headers: Arc<[HeaderName]>,
+}
+
+impl Default for SensitiveHeadersLayer {
+    fn default() -> Self {
+        Self::new([
+            header::AUTHORIZATION,
+            header::PROXY_AUTHORIZATION,
+            header::COOKIE,
+            header::SET_COOKIE,
+            HeaderName::from_static("x-api-key"),
+        ])
+    }
+}
+
+impl SensitiveHeadersLayer {
+    pub fn new(headers: impl IntoIterator<Item = HeaderName>) -> Self {
+        Self {
+            headers: headers.into_iter().collect(),
+        }
+    }
+}
+
+impl<S> Layer<S> for SensitiveHeadersLayer {
+    type Service = SensitiveHeaders<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SensitiveHeaders {
+            inner,
+            headers: self.headers.clone(),
+        }
+    }
+}
+
+/// The service of [`SensitiveHeadersLayer`].
+#[derive(Clone, Debug)]
+pub struct SensitiveHeaders<S> {
+    inner: S,
+    headers: Arc<[HeaderName]>,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for SensitiveHeaders<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+        mark(&self.headers, request.headers_mut());
+        ResponseFuture {
+            inner: self.inner.call(request),
+            headers: self.headers.clone(),
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// The response future of [`SensitiveHeaders`].
+    pub struct ResponseFuture<F> {
+        #[pin]
+        inner: F,
+        headers: Arc<[HeaderName]>,
+    }
+}
+
+impl<F, ResBody, E> Future for ResponseFuture<F>
+where
+    F: Future<Output = Result<Response<ResBody>, E>>,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let mut response = ready!(this.inner.poll(cx))?;
+        mark(this.headers, response.headers_mut());
+        Poll::Ready(Ok(response))
+    }
+}
+
+fn mark(names: &[HeaderName], headers: &mut HeaderMap) {
+    for name in names {
+        if let Entry::Occupied(mut entry) = headers.entry(name) {
+            entry.iter_mut().for_each(|value| value.set_sensitive(true));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tower::{service_fn, ServiceExt};
+
+    #[tokio::test]
+    async fn test_layer() {
+        let service =
+            SensitiveHeadersLayer::default().layer(service_fn(|request: Request<()>| async move {
+                assert!(request.headers()["authorization"].is_sensitive());
+                assert!(!request.headers()["x-request-id"].is_sensitive());
+
+                let response = Response::builder()
+                    .header("set-cookie", "session=foo")
+                    .header("set-cookie", "csrf=bar")
+                    .body(())
+                    .unwrap();
+                Ok::<_, std::convert::Infallible>(response)
+            }));
+
+        let request = Request::builder()
+            .header("authorization", "Bearer secret")
+            .header("x-request-id", "42")
+            .body(())
+            .unwrap();
+        let response = service.oneshot(request).await.unwrap();
+
+        assert!(response
+            .headers()
+            .get_all("set-cookie")
+            .iter()
+            .all(|value| value.is_sensitive()));
+        assert!(!format!("{response:?}").contains("foo"));
+    }
+}
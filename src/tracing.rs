@@ -0,0 +1,210 @@
+//! Integration with `tracing`
+//!
+//! Values wrapped in [`Hide`](crate::Hide) are already hidden when recorded as fields. As a safety
+//! net for values which never were, [`RedactFields`] hides the values of fields based on their
+//! names, independent of their type:
+//!
+//! ```rust
+//! use hide::tracing::RedactFields;
+//!
+//! tracing_subscriber::fmt()
+//!     .fmt_fields(RedactFields::default())
+//!     .init();
+//!
+//! // logs: connecting user="admin" db_password=***
+//! tracing::info!(user = "admin", db_password = "secret", "connecting");
+//! ```
+
+use crate::HideStrict;
+use std::fmt::Debug;
+use std::sync::Arc;
+use tracing_core::field::{Field, Visit};
+use tracing_subscriber::field::{MakeVisitor, VisitFmt, VisitOutput};
+use tracing_subscriber::fmt::format::DefaultFields;
+
+/// Redact the values of fields, whose names contain one of a list of patterns.
+///
+/// Patterns are matched case-insensitively, so that the pattern `token` matches `token`,
+/// `access_token` as well as `TokenValue`. By default, the patterns are `password`, `secret`,
+/// `token`, and `api_key`.
+///
+/// This wraps another field formatter, [`DefaultFields`] by default, and can be used for both
+/// event and span fields of a `fmt` layer.
+#[derive(Clone, Debug)]
+pub struct RedactFields<M = DefaultFields> {
+    inner: M,
+    patterns: Arc<[String]>,
+}
+
+impl Default for RedactFields {
+    fn default() -> Self {
+        Self::new(DefaultFields::new())
+    }
+}
+
+impl<M> RedactFields<M> {
+    /// Wrap a field formatter, using the default patterns.
+    pub fn new(inner: M) -> Self {
+        Self::with_patterns(inner, ["password", "secret", "token", "api_key"])
+    }
+
+    /// Wrap a field formatter, using a custom list of patterns.
+    pub fn with_patterns<I>(inner: M, patterns: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self {
+            inner,
+            patterns: patterns.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Add a pattern.
+    pub fn pattern(self, pattern: impl Into<String>) -> Self {
+        let patterns = self
+            .patterns
+            .iter()
+            .cloned()
+            .chain([pattern.into()])
+            .collect();
+        Self { patterns, ..self }
+    }
+}
+
+impl<T, M: MakeVisitor<T>> MakeVisitor<T> for RedactFields<M> {
+    type Visitor = RedactVisitor<M::Visitor>;
+
+    fn make_visitor(&self, target: T) -> Self::Visitor {
+        RedactVisitor {
+            inner: self.inner.make_visitor(target),
+            patterns: self.patterns.clone(),
+        }
+    }
+}
+
+/// A visitor, redacting fields before passing them on, see [`RedactFields`].
+#[derive(Debug)]
+pub struct RedactVisitor<V> {
+    inner: V,
+    patterns: Arc<[String]>,
+}
+
+impl<V> RedactVisitor<V> {
+    fn redacted(&self, field: &Field) -> bool {
+        crate::sensitive(&self.patterns, field.name())
+    }
+}
+
+/// Forward a value to the inner visitor, unless the field must be redacted.
+macro_rules! forward {
+    ($($name:ident($ty:ty);)*) => {
+        $(
+            fn $name(&mut self, field: &Field, value: $ty) {
+                match self.redacted(field) {
+                    true => self.inner.record_debug(field, &HideStrict(())),
+                    false => self.inner.$name(field, value),
+                }
+            }
+        )*
+    };
+}
+
+impl<V: Visit> Visit for RedactVisitor<V> {
+    forward! {
+        record_f64(f64);
+        record_i64(i64);
+        record_u64(u64);
+        record_i128(i128);
+        record_u128(u128);
+        record_bool(bool);
+        record_str(&str);
+        record_bytes(&[u8]);
+        record_error(&(dyn std::error::Error + 'static));
+        record_debug(&dyn Debug);
+    }
+}
+
+impl<V: VisitOutput<O>, O> VisitOutput<O> for RedactVisitor<V> {
+    fn finish(self) -> O {
+        self.inner.finish()
+    }
+}
+
+impl<V: VisitFmt> VisitFmt for RedactVisitor<V> {
+    fn writer(&mut self) -> &mut dyn std::fmt::Write {
+        self.inner.writer()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io;
+    use std::sync::Mutex;
+
+    #[derive(Clone, Default)]
+    struct Output(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Output {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn capture(fields: RedactFields, f: impl FnOnce()) -> String {
+        let output = Output::default();
+        let writer = output.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .without_time()
+            .with_target(false)
+            .with_level(false)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .fmt_fields(fields)
+            .finish();
+
+        ::tracing::subscriber::with_default(subscriber, f);
+
+        let output = output.0.lock().unwrap();
+        String::from_utf8(output.clone()).unwrap()
+    }
+
+    #[test]
+    fn test_event() {
+        let output = capture(RedactFields::default(), || {
+            ::tracing::info!(
+                user = "admin",
+                DB_Password = "secret",
+                api_key = 42,
+                refresh_token = ?Some("secret"),
+                "connecting"
+            );
+        });
+
+        assert_eq!(
+            output,
+            "connecting user=\"admin\" DB_Password=*** api_key=*** refresh_token=***\n"
+        );
+    }
+
+    #[test]
+    fn test_span() {
+        let fields = RedactFields::with_patterns(DefaultFields::new(), ["pin"]).pattern("");
+        let output = capture(fields, || {
+            let span =
+                ::tracing::info_span!("login", user = "admin", pin = ::tracing::field::Empty);
+            span.record("pin", 1234);
+            span.in_scope(|| ::tracing::info!(password = "secret", "done"));
+        });
+
+        assert_eq!(
+            output,
+            "login{user=\"admin\" pin=***}: done password=\"secret\"\n"
+        );
+    }
+}
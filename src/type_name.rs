@@ -0,0 +1,55 @@
+//! Render the name of the hidden type in the alternate debug output.
+
+/// Get the name of the type, without module paths.
+///
+/// So that `alloc::vec::Vec<alloc::string::String>` becomes `Vec<String>`.
+pub(crate) fn short<T: ?Sized>() -> String {
+    let name = std::any::type_name::<T>();
+    let mut result = String::with_capacity(name.len());
+    let mut segment = 0;
+
+    let mut chars = name.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            ':' if chars.peek() == Some(&':') => {
+                chars.next();
+                result.truncate(segment);
+            }
+            c if c.is_alphanumeric() || c == '_' => result.push(c),
+            c => {
+                result.push(c);
+                segment = result.len();
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_short() {
+        assert_eq!(short::<String>(), "String");
+        assert_eq!(short::<Vec<u8>>(), "Vec<u8>");
+        assert_eq!(
+            short::<HashMap<String, Option<&str>>>(),
+            "HashMap<String, Option<&str>>"
+        );
+        assert_eq!(short::<(u8, [String; 2])>(), "(u8, [String; 2])");
+    }
+
+    #[test]
+    #[cfg(feature = "debug-type")]
+    fn test_debug() {
+        use crate::Hide;
+
+        let password = Hide("foo".to_string());
+        assert_eq!(format!("{password:?}"), "***");
+        assert_eq!(format!("{password:#?}"), "Hide<String>(***)");
+        assert_eq!(format!("{:#?}", Hide(vec![1u8])), "Hide<Vec<u8>>(***)");
+    }
+}
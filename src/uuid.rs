@@ -0,0 +1,88 @@
+//! Support for UUIDs as secrets
+//!
+//! Many API keys are UUIDs. Fully redacting them makes it impossible to tell which one is used,
+//! e.g. when supporting users. [`Hide::redacted`] only shows the last four digits:
+//!
+//! ```rust
+//! use hide::Hide;
+//! use uuid::Uuid;
+//!
+//! let key = Hide::<Uuid>::parse_str("5f8c1e2a-9b3d-4c7e-8a1f-2d6b0e9c4a2f").unwrap();
+//! assert_eq!(format!("{key:?}"), "***");
+//! assert_eq!(format!("{:?}", key.redacted()), "…-****-4a2f");
+//! ```
+//!
+//! With the `serde` feature, `Hide<Uuid>` serializes using the format of [`Uuid`].
+
+use crate::{HiddenParseError, Hide};
+use ::uuid::Uuid;
+use std::fmt::{Debug, Display, Formatter};
+
+impl Hide<Uuid> {
+    /// Parse a UUID, in any of the formats supported by [`Uuid::parse_str`].
+    pub fn parse_str(input: &str) -> Result<Self, HiddenParseError<::uuid::Error>> {
+        input.parse()
+    }
+
+    /// Format as lowercase, hyphenated string, e.g. `5f8c1e2a-9b3d-4c7e-8a1f-2d6b0e9c4a2f`.
+    pub fn to_hyphenated(&self) -> Hide<String> {
+        Hide(self.0.hyphenated().to_string())
+    }
+
+    /// Format as lowercase string without hyphens, e.g. `5f8c1e2a9b3d4c7e8a1f2d6b0e9c4a2f`.
+    pub fn to_simple(&self) -> Hide<String> {
+        Hide(self.0.simple().to_string())
+    }
+
+    /// Only show the last four digits, e.g. `…-****-4a2f`.
+    pub fn redacted(&self) -> RedactedUuid {
+        let bytes = self.0.as_bytes();
+        RedactedUuid([bytes[14], bytes[15]])
+    }
+}
+
+/// A UUID, only showing the last four digits in the [`Display`] and [`Debug`] output.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct RedactedUuid([u8; 2]);
+
+impl Display for RedactedUuid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "…-****-{:02x}{:02x}", self.0[0], self.0[1])
+    }
+}
+
+impl Debug for RedactedUuid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const KEY: &str = "5F8C1E2A-9B3D-4C7E-8A1F-2D6B0E9C4A2F";
+
+    #[test]
+    fn test_parse() {
+        let key = Hide::<Uuid>::parse_str(KEY).unwrap();
+        assert_eq!(
+            key.to_hyphenated().as_str(),
+            "5f8c1e2a-9b3d-4c7e-8a1f-2d6b0e9c4a2f"
+        );
+        assert_eq!(key.to_simple().as_str(), "5f8c1e2a9b3d4c7e8a1f2d6b0e9c4a2f");
+        assert_eq!(key.redacted().to_string(), "…-****-4a2f");
+
+        let err = Hide::<Uuid>::parse_str("5f8c1e2a-9b3d-4c7e-8a1f-2d6b0e9c4a2").unwrap_err();
+        assert!(!err.to_string().contains("5f8c1e2a"));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        let key = Hide::<Uuid>::parse_str(KEY).unwrap();
+        let json = serde_json::to_string(&key).unwrap();
+        assert_eq!(json, r#""5f8c1e2a-9b3d-4c7e-8a1f-2d6b0e9c4a2f""#);
+        assert_eq!(serde_json::from_str::<Hide<Uuid>>(&json).unwrap(), key);
+    }
+}
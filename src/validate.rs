@@ -0,0 +1,116 @@
+use crate::Hide;
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+
+/// A validation of a secret value failed.
+///
+/// Neither [`Display`] nor [`Debug`] contain the value which failed validation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The value was empty.
+    Empty,
+    /// The value was shorter than the required number of bytes.
+    TooShort { min: usize },
+    /// The value was longer than the allowed number of bytes.
+    TooLong { max: usize },
+    /// A custom validation failed.
+    Invalid(Cow<'static, str>),
+}
+
+impl ValidationError {
+    pub fn invalid(reason: impl Into<Cow<'static, str>>) -> Self {
+        Self::Invalid(reason.into())
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => f.write_str("secret must not be empty"),
+            Self::TooShort { min } => write!(f, "secret must be at least {min} bytes long"),
+            Self::TooLong { max } => write!(f, "secret must be at most {max} bytes long"),
+            Self::Invalid(reason) => write!(f, "invalid secret: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl<T> Hide<T> {
+    /// Create a new instance, if the value passes the validator.
+    pub fn try_new_with<F>(value: T, validator: F) -> Result<Self, ValidationError>
+    where
+        F: FnOnce(&T) -> Result<(), ValidationError>,
+    {
+        validator(&value)?;
+        Ok(Self(value))
+    }
+}
+
+impl<T> Hide<T>
+where
+    T: AsRef<[u8]>,
+{
+    /// Create a new instance, if the value is not empty.
+    pub fn try_new_nonempty(value: T) -> Result<Self, ValidationError> {
+        Self::try_new_with(value, |value| match value.as_ref().is_empty() {
+            true => Err(ValidationError::Empty),
+            false => Ok(()),
+        })
+    }
+
+    /// Create a new instance, if the length of the value (in bytes) is within the range.
+    pub fn try_new_len(value: T, range: RangeInclusive<usize>) -> Result<Self, ValidationError> {
+        Self::try_new_with(value, |value| {
+            let len = value.as_ref().len();
+            if len < *range.start() {
+                Err(ValidationError::TooShort {
+                    min: *range.start(),
+                })
+            } else if len > *range.end() {
+                Err(ValidationError::TooLong { max: *range.end() })
+            } else {
+                Ok(())
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_nonempty() {
+        assert!(Hide::try_new_nonempty("foo").is_ok());
+        assert_eq!(
+            Hide::try_new_nonempty(String::new()),
+            Err(ValidationError::Empty)
+        );
+    }
+
+    #[test]
+    fn test_len() {
+        assert!(Hide::try_new_len("foobar", 4..=8).is_ok());
+        assert_eq!(
+            Hide::try_new_len("foo", 4..=8),
+            Err(ValidationError::TooShort { min: 4 })
+        );
+        assert_eq!(
+            Hide::try_new_len(vec![0u8; 16], 4..=8),
+            Err(ValidationError::TooLong { max: 8 })
+        );
+    }
+
+    #[test]
+    fn test_custom() {
+        let err = Hide::try_new_with("hunter2", |value| match value.starts_with("sk-") {
+            true => Ok(()),
+            false => Err(ValidationError::invalid("missing prefix")),
+        })
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "invalid secret: missing prefix");
+    }
+}
@@ -0,0 +1,216 @@
+//! Pass through validation to the inner value
+//!
+//! This allows using the validation attributes of `validator` and `garde` on hidden fields:
+//!
+//! ```rust
+//! # #[cfg(feature = "validator")]
+//! # {
+//! use hide::Hide;
+//! use validator::Validate;
+//!
+//! #[derive(Validate)]
+//! struct Login {
+//!     #[validate(length(min = 8))]
+//!     password: Hide<String>,
+//! }
+//!
+//! assert!(Login { password: "secret".into() }.validate().is_err());
+//! # }
+//! ```
+//!
+//! **Note:** `validator` adds the value of the field to the parameters of the validation error.
+//! With the `serde` feature enabled, this will be the actual value. So the parameters of such
+//! errors must not be logged.
+
+#[cfg(feature = "validator")]
+mod validator_impl {
+    use crate::Hide;
+    use std::borrow::Cow;
+    use validator::{
+        AsRegex, Validate, ValidateContains, ValidateEmail, ValidateLength, ValidateRegex,
+        ValidateUrl, ValidationErrors,
+    };
+
+    impl<T> Validate for Hide<T>
+    where
+        T: Validate,
+    {
+        fn validate(&self) -> Result<(), ValidationErrors> {
+            self.0.validate()
+        }
+    }
+
+    impl<T> ValidateLength<u64> for Hide<T>
+    where
+        T: ValidateLength<u64>,
+    {
+        fn length(&self) -> Option<u64> {
+            self.0.length()
+        }
+    }
+
+    impl<T> ValidateEmail for Hide<T>
+    where
+        T: ValidateEmail,
+    {
+        fn as_email_string(&self) -> Option<Cow<'_, str>> {
+            self.0.as_email_string()
+        }
+    }
+
+    impl<T> ValidateUrl for Hide<T>
+    where
+        T: ValidateUrl,
+    {
+        fn as_url_string(&self) -> Option<Cow<'_, str>> {
+            self.0.as_url_string()
+        }
+    }
+
+    impl<T> ValidateContains for Hide<T>
+    where
+        T: ValidateContains,
+    {
+        fn validate_contains(&self, needle: &str) -> bool {
+            self.0.validate_contains(needle)
+        }
+    }
+
+    impl<T> ValidateRegex for Hide<T>
+    where
+        T: ValidateRegex,
+    {
+        fn validate_regex(&self, regex: impl AsRegex) -> bool {
+            self.0.validate_regex(regex)
+        }
+    }
+}
+
+#[cfg(feature = "garde")]
+mod garde_impl {
+    use crate::Hide;
+    use garde::error::{Path, Report};
+    use garde::rules::length::{HasBytes, HasChars, HasSimpleLength};
+    use garde::rules::AsStr;
+    use garde::Validate;
+
+    impl<T> Validate for Hide<T>
+    where
+        T: Validate,
+    {
+        type Context = T::Context;
+
+        fn validate_into(
+            &self,
+            ctx: &Self::Context,
+            parent: &mut dyn FnMut() -> Path,
+            report: &mut Report,
+        ) {
+            self.0.validate_into(ctx, parent, report)
+        }
+    }
+
+    impl<T> HasSimpleLength for Hide<T>
+    where
+        T: HasSimpleLength,
+    {
+        fn length(&self) -> usize {
+            self.0.length()
+        }
+    }
+
+    impl<T> HasBytes for Hide<T>
+    where
+        T: HasBytes,
+    {
+        fn num_bytes(&self) -> usize {
+            self.0.num_bytes()
+        }
+    }
+
+    impl<T> HasChars for Hide<T>
+    where
+        T: HasChars,
+    {
+        fn num_chars(&self) -> usize {
+            self.0.num_chars()
+        }
+    }
+
+    impl<T> AsStr for Hide<T>
+    where
+        T: AsStr,
+    {
+        fn as_str(&self) -> &str {
+            self.0.as_str()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Hide;
+
+    #[test]
+    #[cfg(feature = "validator")]
+    fn test_validator() {
+        use validator::Validate;
+
+        #[derive(Validate)]
+        struct Login {
+            #[validate(length(min = 8), contains(pattern = "!"))]
+            password: Hide<String>,
+            #[validate(email)]
+            email: Hide<String>,
+        }
+
+        assert!(Login {
+            password: "secret!!".into(),
+            email: "foo@example.com".into(),
+        }
+        .validate()
+        .is_ok());
+
+        let err = Login {
+            password: "secret".into(),
+            email: "foo".into(),
+        }
+        .validate()
+        .unwrap_err();
+
+        let errors = err.field_errors();
+        assert_eq!(errors["password"].len(), 2);
+        assert_eq!(errors["email"].len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "garde")]
+    fn test_garde() {
+        use garde::Validate;
+
+        #[derive(Validate)]
+        struct Login {
+            #[garde(length(min = 8), ascii)]
+            password: Hide<String>,
+            #[garde(length(bytes, max = 4))]
+            pin: Hide<String>,
+        }
+
+        assert!(Login {
+            password: "secret!!".into(),
+            pin: "1234".into(),
+        }
+        .validate()
+        .is_ok());
+
+        let report = Login {
+            password: "secret".into(),
+            pin: "12345".into(),
+        }
+        .validate()
+        .unwrap_err();
+
+        assert_eq!(report.iter().count(), 2);
+        assert!(!report.to_string().contains("secret"));
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{Hide, SUBSTITUTE};
+use std::borrow::Cow;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Deref, DerefMut};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A hidden value, carrying a version and the time it was created.
+///
+/// The metadata is shown in the debug output, the value is not:
+///
+/// ```rust
+/// use hide::Hide;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let created = UNIX_EPOCH + Duration::from_secs(1_714_521_600);
+/// let password = Hide::<String>::versioned("v3", created, "secret");
+/// assert_eq!(format!("{password:?}"), "<v3, created 2024-05-01:***>");
+/// ```
+#[derive(Hash, Eq, PartialEq)]
+#[cfg_attr(not(feature = "paranoid"), derive(Clone))]
+#[cfg_attr(not(feature = "keyed-ord"), derive(Ord, PartialOrd))]
+pub struct VersionedHide<T> {
+    version: Cow<'static, str>,
+    created: SystemTime,
+    value: Hide<T>,
+}
+
+impl<T> VersionedHide<T> {
+    pub fn new(
+        version: impl Into<Cow<'static, str>>,
+        created: SystemTime,
+        value: impl Into<Hide<T>>,
+    ) -> Self {
+        Self {
+            version: version.into(),
+            created,
+            value: value.into(),
+        }
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn created(&self) -> SystemTime {
+        self.created
+    }
+
+    pub fn value(&self) -> &Hide<T> {
+        &self.value
+    }
+
+    pub fn take(self) -> Hide<T> {
+        self.value
+    }
+}
+
+impl<T> Hide<T> {
+    /// Create a new hidden value, carrying a version and the time it was created.
+    pub fn versioned(
+        version: impl Into<Cow<'static, str>>,
+        created: SystemTime,
+        value: impl Into<Hide<T>>,
+    ) -> VersionedHide<T> {
+        VersionedHide::new(version, created, value)
+    }
+}
+
+impl<T> Deref for VersionedHide<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value.0
+    }
+}
+
+impl<T> DerefMut for VersionedHide<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value.0
+    }
+}
+
+impl<T> Display for VersionedHide<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (year, month, day) = date(self.created);
+        write!(
+            f,
+            "<{}, created {year:04}-{month:02}-{day:02}:{SUBSTITUTE}>",
+            self.version
+        )
+    }
+}
+
+impl<T> Debug for VersionedHide<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl<T> From<VersionedHide<T>> for Hide<T> {
+    fn from(value: VersionedHide<T>) -> Self {
+        value.value
+    }
+}
+
+/// The (UTC) date of a point in time, as year, month, and day.
+fn date(time: SystemTime) -> (i64, u32, u32) {
+    let days = match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => (duration.as_secs() / 86400) as i64,
+        Err(err) => -(err.duration().as_secs().div_ceil(86400) as i64),
+    };
+
+    // see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + (month <= 2) as i64;
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_date() {
+        let day = |days: u64| UNIX_EPOCH + Duration::from_secs(days * 86400);
+
+        assert_eq!(date(UNIX_EPOCH), (1970, 1, 1));
+        assert_eq!(date(day(59)), (1970, 3, 1));
+        assert_eq!(date(day(11016)), (2000, 2, 29));
+        assert_eq!(date(day(19844) + Duration::from_secs(86399)), (2024, 5, 1));
+        assert_eq!(date(UNIX_EPOCH - Duration::from_secs(1)), (1969, 12, 31));
+    }
+
+    #[test]
+    fn test_versioned() {
+        let created = UNIX_EPOCH + Duration::from_secs(1_714_521_600);
+        let mut token = Hide::<String>::versioned("AWSCURRENT", created, "foo");
+
+        assert_eq!(format!("{token:?}"), "<AWSCURRENT, created 2024-05-01:***>");
+        assert_eq!(token.version(), "AWSCURRENT");
+        assert_eq!(token.created(), created);
+
+        token.push_str("bar");
+        let token: Hide<String> = token.into();
+        assert_eq!(token.as_str(), "foobar");
+    }
+}
@@ -0,0 +1,87 @@
+//! Integration with `warp`
+//!
+//! ```rust
+//! use hide::Hide;
+//! use warp::Filter;
+//!
+//! let api = hide::warp::bearer_token().map(|token: Hide<String>| {
+//!     // check the token
+//!     "ok"
+//! });
+//! ```
+
+use crate::Hide;
+use ::warp::{reject, Filter, Rejection};
+use std::fmt::{Display, Formatter};
+
+/// Rejection of [`bearer_token`].
+///
+/// This never contains the presented token.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BearerError {
+    /// The `Authorization` header is missing.
+    Missing,
+    /// The `Authorization` header doesn't contain a bearer token.
+    Invalid,
+}
+
+impl Display for BearerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Missing => f.write_str("missing authorization header"),
+            Self::Invalid => f.write_str("invalid bearer token"),
+        }
+    }
+}
+
+impl std::error::Error for BearerError {}
+
+impl reject::Reject for BearerError {}
+
+/// Extract the bearer token of the `Authorization` header.
+///
+/// Rejects with a [`BearerError`] if the header is missing or doesn't contain a bearer token.
+pub fn bearer_token() -> impl Filter<Extract = (Hide<String>,), Error = Rejection> + Clone {
+    ::warp::header::optional::<Hide<String>>("authorization").and_then(
+        |value: Option<Hide<String>>| async move {
+            let value = value.ok_or_else(|| reject::custom(BearerError::Missing))?;
+            parse(&value).ok_or_else(|| reject::custom(BearerError::Invalid))
+        },
+    )
+}
+
+fn parse(value: &Hide<String>) -> Option<Hide<String>> {
+    let (scheme, token) = value.0.split_once(' ')?;
+    let token = token.trim();
+    match scheme.eq_ignore_ascii_case("bearer") && !token.is_empty() {
+        true => Some(Hide(token.to_string())),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    async fn filter(header: Option<&str>) -> Result<Hide<String>, Rejection> {
+        let request = ::warp::test::request();
+        let request = match header {
+            Some(header) => request.header("authorization", header),
+            None => request,
+        };
+        request.filter(&bearer_token()).await
+    }
+
+    #[tokio::test]
+    async fn test_bearer_token() {
+        assert_eq!(filter(Some("Bearer abc")).await.unwrap().as_str(), "abc");
+        assert_eq!(filter(Some("bearer  abc ")).await.unwrap().as_str(), "abc");
+
+        let err = filter(None).await.unwrap_err();
+        assert_eq!(err.find(), Some(&BearerError::Missing));
+
+        let err = filter(Some("Basic secret")).await.unwrap_err();
+        assert_eq!(err.find(), Some(&BearerError::Invalid));
+        assert!(!format!("{err:?}").contains("secret"));
+    }
+}